@@ -1,42 +1,422 @@
 use clap::{Arg as ClapArg, Command as ClapCommand};
-use lib::assistant::Assistant;
-use std::io::{stdin, stdout, Write};
+use lib::assistant::{Assistant, AssistantState};
+use lib::database::{load_pools, normalize_word};
+use lib::suggestion::SortMode;
+use std::error::Error;
+use std::fs::File;
+use std::io::{stdin, stdout, BufReader, BufWriter, Write};
+use std::process;
+
+/// Output formats of the non-interactive mode
+const FORMATS: [&str; 3] = ["plain", "tsv", "json"];
+
+const HELP: &str = "Commands:
+  WORD       enter guessed word, status string follows
+  undo [N]   revert last N guesses, 1 by default
+  history    list guesses entered so far
+  show [N]   list top N suggestions, all by default
+  pick       pick a random word of the ones with most unique letters
+  reset      start over for a new game
+  help       show this help
+  quit       exit
+Commands that are also dictionary words need a colon, e.g. :reset";
+
+/// Loads decision tree into the assistant, exits with a diagnostic on failure
+fn load_tree(assistant: &mut Assistant, path: &str) {
+	if let Err(err) = assistant.load_tree(path) {
+		eprintln!("Failed to load tree \"{}\": {}", path, err);
+		process::exit(1);
+	}
+}
+
+/// Writes guesses entered so far to `path` as JSON
+fn save_state(assistant: &Assistant, path: &str) -> Result<(), Box<dyn Error>> {
+	let mut writer = BufWriter::new(File::create(path)?);
+	serde_json::to_writer_pretty(&mut writer, &assistant.get_state())?;
+	writer.flush()?;
+	Ok(())
+}
+
+/// Saves state if `--save` was given, failure is only reported
+fn autosave(assistant: &Assistant, path: Option<&str>) {
+	if let Some(path) = path {
+		if let Err(err) = save_state(assistant, path) {
+			eprintln!("Failed to save state \"{}\": {}", path, err);
+		}
+	}
+}
+
+/// Reads state written by `save_state`
+fn load_state(path: &str) -> Result<AssistantState, Box<dyn Error>> {
+	let reader = BufReader::new(File::open(path)?);
+	Ok(serde_json::from_reader(reader)?)
+}
+
+/// Prompts and reads a line without the line ending, `None` at the end of input
+fn prompt(text: &str) -> Option<String> {
+	print!("{}", text);
+	let _ = stdout().flush();
+	let mut line = String::new();
+	match stdin().read_line(&mut line) {
+		Ok(0) => None,
+		Ok(_) => Some(line.trim_end_matches(&['\r', '\n'][..]).to_string()),
+		Err(err) => {
+			eprintln!("Failed to read input: {}", err);
+			None
+		}
+	}
+}
+
+/// Parses optional number argument of a command
+fn parse_count(arg: Option<&str>) -> Result<Option<usize>, String> {
+	arg.map(|arg| {
+		arg.parse()
+			.map_err(|_| format!("Expected a number, got \"{}\"", arg))
+	})
+	.transpose()
+}
+
+/// Checks that `word` can be entered as a guess
+fn validate_guess(assistant: &Assistant, word: &str) -> Result<(), String> {
+	let (expected, found) = (assistant.get_word_len(), word.chars().count());
+	if found != expected {
+		return Err(format!(
+			"Guess has {} letters, expected {}",
+			found, expected
+		));
+	}
+	if !word.chars().all(char::is_alphabetic) {
+		return Err("Guess must contain only letters".to_string());
+	}
+	if !assistant.is_known(word) {
+		return Err(format!("\"{}\" not found in dictionary", word));
+	}
+	Ok(())
+}
+
+/// Applies `WORD:STATUS` entry of the non-interactive mode
+fn apply_entry(assistant: &mut Assistant, entry: &str) -> Result<(), Box<dyn Error>> {
+	let (word, status) = entry
+		.split_once(':')
+		.ok_or("expected WORD:STATUS, e.g. crane:gyxxy")?;
+	let word = normalize_word(word);
+	validate_guess(assistant, &word)?;
+	assistant.try_update(&word, status.trim())?;
+	Ok(())
+}
+
+/// Prints up to `limit` suggestions in given `format`, see `FORMATS`
+fn print_suggestions(
+	assistant: &mut Assistant,
+	format: &str,
+	limit: usize,
+) -> Result<(), Box<dyn Error>> {
+	let suggestions = assistant.get_suggestions();
+	let suggestions = &suggestions[..limit.min(suggestions.len())];
+	match format {
+		"json" => println!("{}", serde_json::to_string_pretty(suggestions)?),
+		"tsv" => {
+			println!(
+				"word\tunique_chars\tavg_frequency\tpositional_frequency\tentropy\tworst_case"
+			);
+			for suggestion in suggestions.iter() {
+				println!(
+					"{}\t{}\t{}\t{}\t{}\t{}",
+					suggestion.get_word(),
+					suggestion.get_unique_chars(),
+					suggestion.get_avg_frequency(),
+					suggestion.get_positional_frequency(),
+					suggestion
+						.get_entropy()
+						.map(|entropy| entropy.to_string())
+						.unwrap_or_default(),
+					suggestion
+						.get_worst_case()
+						.map(|worst| worst.to_string())
+						.unwrap_or_default()
+				);
+			}
+		}
+		_ => {
+			for suggestion in suggestions.iter() {
+				println!("{}", suggestion.get_word());
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Prints applied guesses with their statuses
+fn display_history(assistant: &Assistant) {
+	let history = assistant.get_history();
+	if history.is_empty() {
+		println!("No guesses yet");
+	}
+	for (i, (word, status)) in history.iter().enumerate() {
+		println!("{}. {} {}", i + 1, word, status.as_string());
+	}
+	if !history.is_empty() {
+		println!("Constraints: {}", assistant.get_constraints());
+	}
+}
 
 /// Currently used for testing
 fn main() {
 	let args = ClapCommand::new("Wordle Assistant")
-		.arg(ClapArg::new("dict").index(1))
+		.arg(ClapArg::new("dict").index(1).required(true))
 		.after_help("Words dictionary")
+		.arg(
+			ClapArg::new("guesses")
+				.short('g')
+				.value_name("DICT")
+				.long("guesses")
+				.takes_value(true)
+				.help("Allowed guesses dictionary, used to suggest probe words with entropy ranking"),
+		)
+		.arg(
+			ClapArg::new("hard")
+				.long("hard")
+				.help("Hard mode, revealed hints must be used in subsequent guesses"),
+		)
+		.arg(
+			ClapArg::new("sort")
+				.short('s')
+				.value_name("MODE")
+				.long("sort")
+				.takes_value(true)
+				.possible_values(SortMode::VARIANTS)
+				.help("Ranking used to order the suggestions"),
+		)
+		.arg(
+			ClapArg::new("tree")
+				.short('t')
+				.value_name("FILE")
+				.long("tree")
+				.takes_value(true)
+				.help("Decision tree built by the tree tool, its guesses are suggested first while the game follows it"),
+		)
+		.arg(
+			ClapArg::new("seed")
+				.value_name("NUMBER")
+				.long("seed")
+				.takes_value(true)
+				.help("Seed for reproducible random picks of the pick command"),
+		)
+		.arg(
+			ClapArg::new("save")
+				.value_name("FILE")
+				.long("save")
+				.takes_value(true)
+				.help("Saves guesses to FILE after every change, so the session can be resumed"),
+		)
+		.arg(
+			ClapArg::new("resume")
+				.value_name("FILE")
+				.long("resume")
+				.takes_value(true)
+				.help("Resumes session saved with --save, including its hard mode setting"),
+		)
+		.arg(
+			ClapArg::new("guess")
+				.value_name("WORD:STATUS")
+				.long("guess")
+				.takes_value(true)
+				.multiple_occurrences(true)
+				.help("Applies guess and its status, e.g. crane:gyxxy, can be repeated. Prints the suggestions and exits"),
+		)
+		.arg(
+			ClapArg::new("format")
+				.value_name("FORMAT")
+				.long("format")
+				.takes_value(true)
+				.possible_values(FORMATS)
+				.help("Output format of the suggestions, implies non-interactive mode [default: plain]"),
+		)
+		.arg(
+			ClapArg::new("limit")
+				.short('n')
+				.value_name("N")
+				.long("limit")
+				.takes_value(true)
+				.help("Prints at most N suggestions, implies non-interactive mode"),
+		)
 		.get_matches();
 	let path = args
 		.value_of("dict")
 		.expect("dict file expected as argument");
-	let mut assistant = Assistant::new(path);
-	for _ in 0..6 {
-		let mut input = String::new();
-		print!("\nInsert current guess:");
-		let _ = stdout().flush();
-		stdin().read_line(&mut input).expect("Incorrect input"); // TODO: Validate length and that word is not in discarded
-		input.pop();
-		input = input.to_lowercase();
-		let mut status = String::new();
-		print!("Insert status string [GYX]:");
-		let _ = stdout().flush();
-		stdin().read_line(&mut status).expect("Incorrect input"); // TODO: Validate length and that word is not in discarded
-		status.pop();
-		status = status.to_uppercase();
-		if status == "GGGGG" {
-			println!("Wordle solved.");
-			break;
-		}
-		assistant.update(&input, &status);
-		println!("");
-		if assistant.suggestions_empty() {
-			println!("Out of suggestions, did you win?");
-			break;
+	let (solutions, guesses) = load_pools(path, args.value_of("guesses")).unwrap_or_else(|err| {
+		eprintln!("Failed to load dictionary {}", err);
+		process::exit(1);
+	});
+	let mut assistant = Assistant::from_databases(solutions, guesses);
+	assistant.set_hard_mode(args.is_present("hard"));
+	if let Some(mode) = args.value_of("sort") {
+		assistant.set_sort_mode(mode.parse().unwrap());
+	}
+	if let Some(path) = args.value_of("tree") {
+		load_tree(&mut assistant, path);
+	}
+	match args.value_of("seed").map(str::parse::<u64>) {
+		Some(Ok(seed)) => assistant.set_seed(seed),
+		Some(Err(_)) => {
+			eprintln!("--seed expects a number");
+			process::exit(1);
+		}
+		None => {}
+	}
+	let save = args.value_of("save");
+	// Guesses or output options given on the command line, nothing is asked
+	let scripted = ["guess", "format", "limit"]
+		.iter()
+		.any(|arg| args.is_present(arg));
+	if let Some(path) = args.value_of("resume") {
+		let state = match load_state(path) {
+			Ok(state) => state,
+			Err(err) => {
+				eprintln!("Failed to resume \"{}\": {}", path, err);
+				process::exit(1);
+			}
+		};
+		let restored = assistant.restore(&state);
+		if restored < state.history.len() {
+			eprintln!(
+				"Skipped {} guess(es) not found in dictionary",
+				state.history.len() - restored
+			);
+		}
+		if !scripted {
+			display_history(&assistant);
+			if restored > 0 {
+				println!();
+				assistant.display_suggestions();
+			}
+		}
+		autosave(&assistant, save);
+	}
+	if scripted {
+		let limit = match args.value_of("limit").map(str::parse::<usize>) {
+			Some(Ok(limit)) => limit,
+			Some(Err(_)) => {
+				eprintln!("--limit expects a number");
+				process::exit(1);
+			}
+			None => usize::MAX,
+		};
+		for entry in args.values_of("guess").into_iter().flatten() {
+			if let Err(err) = apply_entry(&mut assistant, entry) {
+				eprintln!("Invalid guess \"{}\": {}", entry, err);
+				process::exit(1);
+			}
+		}
+		autosave(&assistant, save);
+		let format = args.value_of("format").unwrap_or("plain");
+		if let Err(err) = print_suggestions(&mut assistant, format, limit) {
+			eprintln!("Failed to print suggestions: {}", err);
+			process::exit(1);
+		}
+		return;
+	}
+	if assistant.get_history().is_empty() {
+		if let Some(guess) = assistant.get_tree_guess() {
+			println!("Decision tree suggests: {}", guess);
+		}
+	}
+	println!("\nType help to list commands");
+	while let Some(input) = prompt("\nInsert current guess [or command]:") {
+		let input = normalize_word(&input);
+		let prefixed = input.strip_prefix(':');
+		let mut command = prefixed.unwrap_or(&input).split_whitespace();
+		let (name, arg) = (command.next(), command.next());
+		match name {
+			// Dictionary words are guesses, colon makes them commands, e.g. `:reset`
+			_ if prefixed.is_none() && assistant.is_known(&input) => {}
+			None => continue,
+			Some("quit") | Some("exit") => break,
+			Some("help") => {
+				println!("{}", HELP);
+				continue;
+			}
+			Some("undo") => {
+				let count = match parse_count(arg) {
+					Ok(count) => count,
+					Err(err) => {
+						println!("{}", err);
+						continue;
+					}
+				};
+				let undone = assistant.undo(count.unwrap_or(1));
+				autosave(&assistant, save);
+				println!("Reverted {} update(s)\n", undone);
+				display_history(&assistant);
+				continue;
+			}
+			Some("history") => {
+				display_history(&assistant);
+				continue;
+			}
+			Some("show") => {
+				let count = match parse_count(arg) {
+					Ok(count) => count,
+					Err(err) => {
+						println!("{}", err);
+						continue;
+					}
+				};
+				assistant.display_top_suggestions(count.unwrap_or(usize::MAX));
+				continue;
+			}
+			Some("pick") => {
+				match assistant.get_random() {
+					Some(word) => println!("Random pick: {}", word),
+					None => println!("Out of suggestions"),
+				}
+				continue;
+			}
+			Some("reset") => {
+				assistant.reset();
+				autosave(&assistant, save);
+				println!("Started over, all words are possible again");
+				if let Some(guess) = assistant.get_tree_guess() {
+					println!("Decision tree suggests: {}", guess);
+				}
+				continue;
+			}
+			Some(name) if prefixed.is_some() => {
+				println!("Unknown command \"{}\", type help to list commands", name);
+				continue;
+			}
+			_ => {}
+		}
+		if let Err(err) = validate_guess(&assistant, &input) {
+			println!("{}, type help to list commands", err);
+			continue;
+		}
+		// Status is asked again until valid, empty line goes back to the guess
+		let status = loop {
+			match prompt("Insert status string [GYX]:") {
+				None => return,
+				Some(status) if status.trim().is_empty() => break None,
+				Some(status) => match assistant.try_update(&input, status.trim()) {
+					Ok(()) => break Some(status.trim().to_uppercase()),
+					Err(err) => println!("{}", err),
+				},
+			}
+		};
+		let status = match status {
+			Some(status) => status,
+			None => continue,
+		};
+		autosave(&assistant, save);
+		println!();
+		if status == "G".repeat(assistant.get_word_len()) {
+			println!(
+				"Wordle solved in {} guess(es). Type reset for a new game or quit",
+				assistant.get_history().len()
+			);
+		} else if assistant.suggestions_empty() {
+			println!("Out of suggestions, did you win? Mistyped status can be reverted with undo");
 		} else {
 			assistant.display_suggestions();
-			// println!("Best {}", assistant.get_most_freq().unwrap());
 		}
 	}
 }
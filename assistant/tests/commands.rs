@@ -0,0 +1,53 @@
+//! Commands of the interactive assistant, fed through stdin
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Runs the assistant on `dict` with `input` typed line by line, returns stdout
+fn run(dict: &str, input: &str) -> String {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_assistant"))
+		.arg(dict)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.spawn()
+		.expect("Failed to start assistant");
+	child
+		.stdin
+		.take()
+		.unwrap()
+		.write_all(input.as_bytes())
+		.unwrap();
+	let output = child.wait_with_output().unwrap();
+	assert!(output.status.success());
+	String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn command_name_in_dictionary_is_a_guess() {
+	let output = run(
+		"../data/possible_words.txt",
+		"reset\nxxxxx\nhistory\nquit\n",
+	);
+	assert!(!output.contains("Started over"));
+	assert!(output.contains("1. reset XXXXX"));
+}
+
+#[test]
+fn colon_makes_dictionary_word_a_command() {
+	let output = run(
+		"../data/possible_words.txt",
+		"reset\nxxxxx\n:reset\nhistory\nquit\n",
+	);
+	assert!(output.contains("Started over"));
+	assert!(output.contains("No guesses yet"));
+}
+
+#[test]
+fn command_name_not_in_dictionary_is_a_command() {
+	let output = run(
+		"../data/test_dict.txt",
+		"ccccc\nxxxxx\nreset\nhistory\nquit\n",
+	);
+	assert!(output.contains("Started over"));
+	assert!(output.contains("No guesses yet"));
+}
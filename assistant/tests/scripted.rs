@@ -0,0 +1,42 @@
+//! Non-interactive mode of the assistant, no input is read
+
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str]) -> Output {
+	Command::new(env!("CARGO_BIN_EXE_assistant"))
+		.arg("../data/possible_words.txt")
+		.args(args)
+		.stdin(Stdio::null())
+		.output()
+		.expect("Failed to start assistant")
+}
+
+#[test]
+fn guesses_narrow_down_suggestions() {
+	let output = run(&["--guess", "crane:gyxxy", "--guess", "cover:ggxgg"]);
+	assert!(output.status.success());
+	let words: Vec<String> = String::from_utf8(output.stdout)
+		.unwrap()
+		.lines()
+		.map(str::to_string)
+		.collect();
+	assert_eq!(words.len(), 2);
+	assert!(words.contains(&"corer".to_string()));
+	assert!(words.contains(&"cower".to_string()));
+}
+
+#[test]
+fn limit_alone_is_non_interactive() {
+	let output = run(&["--limit", "3"]);
+	assert!(output.status.success());
+	let stdout = String::from_utf8(output.stdout).unwrap();
+	assert_eq!(stdout.lines().count(), 3);
+	assert!(!stdout.contains("Insert current guess"));
+}
+
+#[test]
+fn invalid_guess_fails() {
+	let output = run(&["--guess", "crane:gyx"]);
+	assert!(!output.status.success());
+	assert!(output.stdout.is_empty());
+}
@@ -0,0 +1,286 @@
+use clap::{Arg as ClapArg, Command as ClapCommand};
+use lib::assistant::Assistant;
+use lib::database::{load_pools, Database};
+use lib::game::Wordle;
+use lib::pattern::PatternMatrix;
+use lib::suggestion::SortMode;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::process;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Games not solved within this many guesses are given up
+const MAX_ROUNDS: usize = 20;
+/// Guesses allowed by the official game, more is a loss
+const WIN_ATTEMPTS: usize = 6;
+const HISTOGRAM_WIDTH: usize = 50;
+const DEFAULT_WORST: usize = 10;
+
+/// Outcome of a single played game
+struct GameResult {
+	word: String,
+	/// Number of guesses needed, `None` if not solved within `MAX_ROUNDS`
+	guesses: Option<usize>,
+}
+
+impl GameResult {
+	fn is_win(&self) -> bool {
+		self.guesses.is_some_and(|n| n <= WIN_ATTEMPTS)
+	}
+}
+
+/// Results of one strategy over the whole solution list
+struct Report {
+	mode: SortMode,
+	opener: String,
+	opener_time: Duration,
+	total_time: Duration,
+	results: Vec<GameResult>,
+}
+
+impl Report {
+	fn display(&self, worst: usize) {
+		let games = self.results.len();
+		let won = self.results.iter().filter(|r| r.is_win()).count();
+		let solved: Vec<usize> = self.results.iter().filter_map(|r| r.guesses).collect();
+		let mean = solved.iter().sum::<usize>() as f32 / solved.len().max(1) as f32;
+		println!("\n== {:?} ==", self.mode);
+		println!(
+			"Opening guess: {} (computed in {:.2?})",
+			self.opener, self.opener_time
+		);
+		println!(
+			"Games: {}  Won: {} ({:.2}%)  Mean guesses: {:.3}",
+			games,
+			won,
+			100.0 * won as f32 / games.max(1) as f32,
+			mean
+		);
+		println!(
+			"Time: {:.2?} total, {:.2?} per game",
+			self.total_time,
+			self.total_time / games.max(1) as u32
+		);
+
+		println!("Guess distribution:");
+		let max_guesses = solved.iter().copied().max().unwrap_or(0);
+		let mut histogram = vec![0; max_guesses + 1];
+		for n in solved.iter() {
+			histogram[*n] += 1;
+		}
+		let unsolved = games - solved.len();
+		let peak = histogram
+			.iter()
+			.copied()
+			.max()
+			.unwrap_or(0)
+			.max(unsolved)
+			.max(1);
+		for (n, count) in histogram.iter().enumerate().skip(1) {
+			display_bar(&n.to_string(), *count, peak);
+		}
+		if unsolved > 0 {
+			display_bar("X", unsolved, peak);
+		}
+
+		let mut sorted: Vec<&GameResult> = self.results.iter().collect();
+		// Ties by word, so the list doesn't depend on play order
+		sorted.sort_by(|a, b| {
+			let guesses = |r: &GameResult| r.guesses.unwrap_or(usize::MAX);
+			guesses(b)
+				.cmp(&guesses(a))
+				.then_with(|| a.word.cmp(&b.word))
+		});
+		let worst: Vec<String> = sorted
+			.iter()
+			.take(worst)
+			.map(|r| match r.guesses {
+				Some(n) => format!("{} ({})", r.word, n),
+				None => format!("{} (X)", r.word),
+			})
+			.collect();
+		println!("Worst words: {}", worst.join(", "));
+	}
+}
+
+fn display_bar(label: &str, count: usize, peak: usize) {
+	println!(
+		"{:>3} | {:>6} | {}",
+		label,
+		count,
+		"#".repeat(count * HISTOGRAM_WIDTH / peak)
+	);
+}
+
+/// Plays one game against hidden `word` starting with `opener`
+///
+/// Returns number of guesses needed or `None` if not solved
+fn play(assistant: &mut Assistant, word: &str, opener: &str) -> Option<usize> {
+	assistant.reset();
+	let mut wordle = Wordle::new(word.to_string());
+	wordle.set_max_attempts(MAX_ROUNDS);
+	wordle.set_hard_mode(assistant.is_hard_mode());
+	let mut guess = opener.to_string();
+	for round in 1..=MAX_ROUNDS {
+		let status = wordle
+			.guess_word(&guess)
+			.expect("Assistant suggested invalid guess");
+		if status.is_correct() {
+			return Some(round);
+		}
+		assistant
+			.try_update(&guess, &status.as_string())
+			.expect("Game rejected status of its own guess");
+		guess = assistant.get_best()?;
+	}
+	None
+}
+
+fn run(
+	mode: SortMode,
+	solutions: &Database,
+	guesses: &Option<Database>,
+	targets: &[String],
+	hard: bool,
+	patterns: &Arc<PatternMatrix>,
+) -> Report {
+	let mut assistant = Assistant::from_databases(solutions.clone(), guesses.clone());
+	assistant.set_patterns(Some(patterns.clone()));
+	assistant.set_sort_mode(mode);
+	assistant.set_hard_mode(hard);
+	// Opening guess is the same for every game, so it is computed only once
+	let start = Instant::now();
+	let opener = assistant.get_best().expect("Empty dictionary");
+	let opener_time = start.elapsed();
+	let start = Instant::now();
+	let results = targets
+		.iter()
+		.map(|word| GameResult {
+			word: word.clone(),
+			guesses: play(&mut assistant, word, &opener),
+		})
+		.collect();
+	Report {
+		mode,
+		opener,
+		opener_time,
+		total_time: start.elapsed(),
+		results,
+	}
+}
+
+/// Plays every word in the solutions dictionary with each selected strategy
+fn main() {
+	let args = ClapCommand::new("Wordle Bench")
+		.arg(ClapArg::new("dict").index(1).required(true))
+		.after_help("Words dictionary, every word is played as the hidden one")
+		.arg(
+			ClapArg::new("guesses")
+				.short('g')
+				.value_name("DICT")
+				.long("guesses")
+				.takes_value(true)
+				.help(
+					"Allowed guesses dictionary, used to suggest probe words with entropy ranking",
+				),
+		)
+		.arg(
+			ClapArg::new("hard")
+				.long("hard")
+				.help("Hard mode, revealed hints must be used in subsequent guesses"),
+		)
+		.arg(
+			ClapArg::new("sort")
+				.short('s')
+				.value_name("MODE")
+				.long("sort")
+				.takes_value(true)
+				.multiple_occurrences(true)
+				.possible_values(SortMode::VARIANTS)
+				.help("Strategy to benchmark, can be repeated. All strategies by default"),
+		)
+		.arg(
+			ClapArg::new("limit")
+				.short('n')
+				.value_name("N")
+				.long("limit")
+				.takes_value(true)
+				.help("Play only first N words of the dictionary, or N random words with --seed"),
+		)
+		.arg(
+			ClapArg::new("seed")
+				.value_name("NUMBER")
+				.long("seed")
+				.takes_value(true)
+				.help("Seed for reproducible random sample of words to play"),
+		)
+		.arg(
+			ClapArg::new("worst")
+				.short('w')
+				.value_name("N")
+				.long("worst")
+				.takes_value(true)
+				.help("Number of worst words to show"),
+		)
+		.get_matches();
+	let path = args
+		.value_of("dict")
+		.expect("dict file expected as argument");
+	let (solutions, guesses) = load_pools(path, args.value_of("guesses")).unwrap_or_else(|err| {
+		eprintln!("Failed to load dictionary {}", err);
+		process::exit(1);
+	});
+	let limit = match args.value_of("limit").map(str::parse::<usize>) {
+		Some(Ok(limit)) => limit,
+		Some(Err(_)) => {
+			eprintln!("--limit expects a number");
+			process::exit(1);
+		}
+		None => solutions.get_len(),
+	};
+	let worst = match args.value_of("worst").map(str::parse::<usize>) {
+		Some(Ok(worst)) => worst,
+		Some(Err(_)) => {
+			eprintln!("--worst expects a number");
+			process::exit(1);
+		}
+		None => DEFAULT_WORST,
+	};
+	let modes: Vec<SortMode> = match args.values_of("sort") {
+		Some(values) => values.map(|mode| mode.parse().unwrap()).collect(),
+		None => SortMode::VARIANTS
+			.iter()
+			.map(|mode| mode.parse().unwrap())
+			.collect(),
+	};
+	let seed = match args.value_of("seed").map(str::parse::<u64>) {
+		Some(Ok(seed)) => Some(seed),
+		Some(Err(_)) => {
+			eprintln!("--seed expects a number");
+			process::exit(1);
+		}
+		None => None,
+	};
+	let hard = args.is_present("hard");
+	let mut targets: Vec<String> = solutions.get_available().iter().cloned().collect();
+	if let Some(seed) = seed {
+		targets.shuffle(&mut StdRng::seed_from_u64(seed));
+	}
+	targets.truncate(limit);
+
+	println!(
+		"Playing {} words, {} letters each{}",
+		targets.len(),
+		solutions.get_word_len(),
+		if hard { ", hard mode" } else { "" }
+	);
+	// Feedback matrix is the same for every strategy, so it is computed only once
+	let start = Instant::now();
+	let patterns = Assistant::from_databases(solutions.clone(), guesses.clone()).precompute_patterns();
+	println!("Feedback patterns computed in {:.2?}", start.elapsed());
+	for mode in modes {
+		run(mode, &solutions, &guesses, &targets, hard, &patterns).display(worst);
+	}
+}
@@ -18,14 +18,36 @@ pub struct TileSize {
 #[derive(Component)]
 pub struct Value(pub String);
 
+/// What a key of the on-screen keyboard types
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyValue {
+    Letter(char),
+    Enter,
+    Back,
+}
+
 #[derive(Component)]
-pub struct TextTileValue;
+pub struct Key(pub KeyValue);
 
+/// Place of a key, `center` and `width` in multiples of a letter key width
 #[derive(Component)]
-pub struct VictoryScreen;
+pub struct KeyLayout {
+    pub row: usize,
+    pub center: f32,
+    pub width: f32,
+}
 
 #[derive(Component)]
+pub struct TextTileValue;
+
+#[derive(Component, Clone, Copy)]
+pub struct VictoryScreen;
+
+#[derive(Component, Clone, Copy)]
 pub struct GameOverScreen;
 
 #[derive(Component)]
 pub struct IncorrectWordScreen;
+
+#[derive(Component, Clone, Copy)]
+pub struct StatsScreen;
@@ -7,12 +7,25 @@ pub const TILE_GREEN_COLOR: Color = Color::rgb(36.0 / 255.0, 130.0 / 255.0, 50.0
 pub const TILE_YELLOW_COLOR: Color = Color::rgb(255.0 / 255.0, 184.0 / 255.0, 0.0 / 255.0);
 pub const TILE_GREY_COLOR: Color = Color::rgb(57.0 / 255.0, 61.0 / 255.0, 63.0 / 255.0);
 
+// Keyboard
+pub const KEY_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+pub const KEY_DEFAULT_COLOR: Color = Color::rgb(180.0 / 255.0, 184.0 / 255.0, 186.0 / 255.0);
+pub const KEY_FONT_SIZE: f32 = 24.0;
+// Width of enter and backspace keys in letter key widths
+pub const KEY_WIDE: f32 = 1.5;
+// Share of the window height taken by the tiles, keyboard is below them
+pub const BOARD_SHARE: f32 = 0.65;
+
 // Font
 pub const FONT_PATH: &str = "fonts/VCR_OSD_MONO.ttf";
 pub const FONT_SIZE: f32 = 60.0;
 pub const FONT_COLOR: Color = Color::rgb(0.0 / 255.0, 0.0 / 255.0, 0.0 / 255.0);
 
 // Puzzle
-pub const WIDTH: f32 = 5.0;
+// Width is given by the word length of loaded dictionary
 pub const HEIGHT: f32 = 6.0;
+pub const SHARE_TILE_SIZE: f32 = 30.0;
+pub const SHARE_TILE_GAP: f32 = 6.0;
+pub const STATS_BAR_WIDTH: f32 = 450.0;
+pub const STATS_BAR_HEIGHT: f32 = 35.0;
 pub const BACKGROUND: Color = Color::rgba(0.15, 0.15, 0.15, 0.9);
@@ -1,11 +1,13 @@
 use crate::components::*;
 use crate::consts::*;
-use crate::resources::{CurrentGuess, Cursor, Game, GameStatus};
+use crate::keyboard_plugin::VirtualKey;
+use crate::resources::{CurrentGuess, Cursor, Game, GameStatus, RejectedGuess};
 use crate::states::GameState;
 use crate::utils::despawn_screen;
 use bevy::input::keyboard::{KeyCode, KeyboardInput};
 use bevy::input::ElementState;
 use bevy::prelude::*;
+use lib::database::normalize_word;
 
 pub struct GamePlugin;
 
@@ -41,26 +43,36 @@ fn gameover_check(mut state: ResMut<State<GameState>>, game: Res<Game>) {
 }
 
 /// System to handle resizing tiles based on window size
-fn tile_size_system(win: Res<Windows>, mut q: Query<(&TileSize, &mut Sprite), With<Tile>>) {
+fn tile_size_system(
+	win: Res<Windows>,
+	game: Res<Game>,
+	mut q: Query<(&TileSize, &mut Sprite), With<Tile>>,
+) {
 	let w = win.get_primary().unwrap().width() as f32;
-	let h = win.get_primary().unwrap().height() as f32;
+	let h = win.get_primary().unwrap().height() as f32 * BOARD_SHARE;
+	let width = game.word_len() as f32;
 	for (size, mut sprite) in q.iter_mut() {
-		let sx = (w / WIDTH) * size.x;
+		let sx = (w / width) * size.x;
 		let sy = (h / HEIGHT) * size.y;
 		sprite.custom_size = Some(Vec2::new(sx, sy));
 	}
 }
 
 /// System to handle tiles positioning based - translates grid based to window based locations
+///
+/// Tiles take the top `BOARD_SHARE` of the window, the keyboard is below them.
 fn tile_position_system(
 	win: Res<Windows>,
+	game: Res<Game>,
 	mut q: Query<(&mut Transform, &TilePosition), With<Tile>>,
 ) {
 	let w = win.get_primary().unwrap().width() as f32;
-	let h = win.get_primary().unwrap().height() as f32;
+	let window_h = win.get_primary().unwrap().height() as f32;
+	let h = window_h * BOARD_SHARE;
+	let width = game.word_len() as f32;
 	for (mut tx, pos) in q.iter_mut() {
-		let x = (pos.col as f32 / WIDTH) * w - w / 2.0 + (w / WIDTH) / 2.0;
-		let y = -1.0 * (pos.row as f32 / HEIGHT) * h + h / 2.0 - (h / HEIGHT) / 2.0;
+		let x = (pos.col as f32 / width) * w - w / 2.0 + (w / width) / 2.0;
+		let y = -1.0 * (pos.row as f32 / HEIGHT) * h + window_h / 2.0 - (h / HEIGHT) / 2.0;
 		tx.translation = Vec3::new(x, y, 0.0);
 	}
 }
@@ -101,12 +113,12 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>, game: Res<Game>
 	};
 	for row in 0..HEIGHT as usize {
 		let guessed = game.guesses[row].as_ref();
-		for col in 0..WIDTH as usize {
+		for col in 0..game.word_len() {
 			let value = match guessed {
 				Some(val) => val.chars().nth(col).unwrap(),
 				None => ' ',
 			};
-			let label = format!("{}", value);
+			let label = format!("{}", value).to_uppercase();
 			// println!("[{}, {}]: {}", row, col, label);
 			commands
 				.spawn_bundle(SpriteBundle {
@@ -143,8 +155,11 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>, game: Res<Game>
 }
 
 /// This currently contains all the logic, which shouldn't be the case
+///
+/// Keys clicked on the on-screen keyboard are handled as typed.
 fn keyboard_input(
 	mut char_evr: EventReader<ReceivedCharacter>,
+	mut key_evr: EventReader<VirtualKey>,
 	mut keys: ResMut<Input<KeyCode>>,
 	mut guess: ResMut<CurrentGuess>,
 	mut cursor: ResMut<Cursor>,
@@ -152,12 +167,24 @@ fn keyboard_input(
 	mut text_q: Query<(&mut Text, &TilePosition), With<TextTileValue>>,
 	mut game: ResMut<Game>,
 	mut state: ResMut<State<GameState>>,
+	mut rejected: ResMut<RejectedGuess>,
 ) {
-	for ev in char_evr.iter() {
-		// println!("Got char: '{}'", ev.char);
-		if ev.char.is_ascii_alphabetic() && guess.word.len() < 5 {
-			let label = format!("{}", ev.char).to_uppercase();
-			guess.word.push(ev.char);
+	let word_len = game.word_len();
+	let mut chars: Vec<char> = char_evr.iter().map(|ev| ev.char).collect();
+	let mut enter = keys.just_released(KeyCode::Return);
+	let mut back = keys.just_released(KeyCode::Back);
+	for ev in key_evr.iter() {
+		match ev.0 {
+			KeyValue::Letter(c) => chars.push(c),
+			KeyValue::Enter => enter = true,
+			KeyValue::Back => back = true,
+		}
+	}
+	for c in chars {
+		// println!("Got char: '{}'", c);
+		if c.is_alphabetic() && guess.word.chars().count() < word_len {
+			let label = format!("{}", c).to_uppercase();
+			guess.word.push(c);
 			for (mut text, pos) in text_q.iter_mut() {
 				if cursor.position == *pos {
 					text.sections[0].value = label.clone();
@@ -168,14 +195,14 @@ fn keyboard_input(
 					val.0 = label.clone();
 				}
 			}
-			cursor.position.col = (cursor.position.col + 1).min(5);
+			cursor.position.col = (cursor.position.col + 1).min(word_len);
 			// println!("Cursor {:?}", cursor.position);
 		}
 	}
 
-	if keys.just_released(KeyCode::Return) && guess.word.len() == 5 {
+	if enter && guess.word.chars().count() == word_len {
 		// println!("Text input: {}", guess.word);
-		match game.make_guess_simple(&guess.word.to_lowercase()) {
+		match game.make_guess_simple(&normalize_word(&guess.word)) {
 			GameStatus::Ok(val) => {
 				game.colors[cursor.position.row] = val;
 				guess.word.clear();
@@ -184,6 +211,11 @@ fn keyboard_input(
 			}
 			GameStatus::InvalidWord => {
 				// println!("Invalid word");
+				*rejected = RejectedGuess::default();
+				state.push(GameState::IncorrectWord).unwrap();
+			}
+			GameStatus::HardModeViolation(reason) => {
+				rejected.0 = reason;
 				state.push(GameState::IncorrectWord).unwrap();
 			}
 			GameStatus::GameOver => {
@@ -196,10 +228,12 @@ fn keyboard_input(
 				// state.set(GameState::Victory).unwrap();
 			}
 		}
+		// The Enter submitting the last guess must not also leave the end screen
+		keys.reset(KeyCode::Return);
 		// println!("Cursor {:?}", cursor.position);
 	}
 
-	if keys.just_released(KeyCode::Back) {
+	if back {
 		// println!("Removing last letter");
 		guess.word.pop();
 		if cursor.position.col != 0 {
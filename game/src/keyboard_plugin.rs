@@ -0,0 +1,161 @@
+use crate::components::*;
+use crate::consts::*;
+use crate::resources::Game;
+use crate::states::GameState;
+use crate::utils::despawn_screen;
+use bevy::prelude::*;
+
+/// Key of the on-screen keyboard clicked with the mouse
+pub struct VirtualKey(pub KeyValue);
+
+/// On-screen keyboard below the tiles
+///
+/// Keys are colored by the best status their letter got so far. Clicking them
+/// sends `VirtualKey` events, which the `GamePlugin` handles as typed keys.
+pub struct KeyboardPlugin;
+
+impl Plugin for KeyboardPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_event::<VirtualKey>()
+			.add_system_set(
+				SystemSet::on_enter(GameState::Restarting).with_system(despawn_screen::<Key>),
+			)
+			.add_system_set(SystemSet::on_enter(GameState::InGame).with_system(keyboard_setup))
+			.add_system_set(
+				SystemSet::on_update(GameState::InGame)
+					.with_system(key_layout_system)
+					.with_system(key_color_system)
+					.with_system(key_click_system),
+			);
+	}
+}
+
+/// QWERTY rows, enter and backspace around the last one, and a row of
+/// the dictionary's letters missing on QWERTY if there are any
+fn keyboard_rows(game: &Game) -> Vec<Vec<(KeyValue, f32)>> {
+	let letters = |row: &str| -> Vec<(KeyValue, f32)> {
+		row.chars().map(|c| (KeyValue::Letter(c), 1.0)).collect()
+	};
+	let mut rows: Vec<Vec<(KeyValue, f32)>> = Vec::new();
+	rows.push(letters(KEY_ROWS[0]));
+	rows.push(letters(KEY_ROWS[1]));
+	let mut last = vec![(KeyValue::Enter, KEY_WIDE)];
+	last.extend(letters(KEY_ROWS[2]));
+	last.push((KeyValue::Back, KEY_WIDE));
+	rows.push(last);
+	let extra = game.get_extra_letters();
+	if !extra.is_empty() {
+		rows.push(extra.iter().map(|c| (KeyValue::Letter(*c), 1.0)).collect());
+	}
+	rows
+}
+
+fn keyboard_setup(mut commands: Commands, asset_server: Res<AssetServer>, game: Res<Game>) {
+	let font_handle: Handle<Font> = asset_server.load(FONT_PATH);
+	let text_style = TextStyle {
+		font: font_handle,
+		font_size: KEY_FONT_SIZE,
+		color: FONT_COLOR,
+	};
+	let text_alignment = TextAlignment {
+		vertical: VerticalAlign::Center,
+		horizontal: HorizontalAlign::Center,
+	};
+	for (row, keys) in keyboard_rows(&game).into_iter().enumerate() {
+		let row_width: f32 = keys.iter().map(|(_, width)| width).sum();
+		let mut left = -row_width / 2.0;
+		for (value, width) in keys {
+			let label = match value {
+				KeyValue::Letter(c) => c.to_uppercase().to_string(),
+				KeyValue::Enter => "ENTER".to_string(),
+				KeyValue::Back => "BACK".to_string(),
+			};
+			commands
+				.spawn_bundle(SpriteBundle {
+					sprite: Sprite {
+						color: KEY_DEFAULT_COLOR,
+						..Default::default()
+					},
+					..Default::default()
+				})
+				.with_children(|parent| {
+					parent.spawn_bundle(Text2dBundle {
+						text: Text::with_section(label, text_style.clone(), text_alignment),
+						transform: Transform {
+							translation: Vec3::new(0.0, 0.0, 1.0),
+							..Default::default()
+						},
+						..Default::default()
+					});
+				})
+				.insert(Key(value))
+				.insert(KeyLayout {
+					row,
+					center: left + width / 2.0,
+					width,
+				});
+			left += width;
+		}
+	}
+}
+
+/// Fits the keyboard to the window area below the tiles
+fn key_layout_system(
+	win: Res<Windows>,
+	mut q: Query<(&KeyLayout, &mut Transform, &mut Sprite), With<Key>>,
+) {
+	let w = win.get_primary().unwrap().width();
+	let h = win.get_primary().unwrap().height();
+	let rows = q
+		.iter()
+		.map(|(layout, _, _)| layout.row + 1)
+		.max()
+		.unwrap_or(1);
+	let widest = q
+		.iter()
+		.map(|(layout, _, _)| layout.center.abs() + layout.width / 2.0)
+		.fold(0.0, f32::max);
+	// Letter key width, half a key margin on both sides
+	let unit = w / (2.0 * widest + 1.0);
+	let key_h = h * (1.0 - BOARD_SHARE) / rows as f32;
+	for (layout, mut tx, mut sprite) in q.iter_mut() {
+		let y = -h / 2.0 + key_h * (rows - layout.row) as f32 - key_h / 2.0;
+		tx.translation = Vec3::new(layout.center * unit, y, 0.0);
+		sprite.custom_size = Some(Vec2::new(layout.width * unit * 0.9, key_h * 0.85));
+	}
+}
+
+fn key_color_system(game: Res<Game>, mut q: Query<(&Key, &mut Sprite)>) {
+	for (key, mut sprite) in q.iter_mut() {
+		sprite.color = match key.0 {
+			KeyValue::Letter(c) => game.get_letter_color(c).unwrap_or(KEY_DEFAULT_COLOR),
+			_ => KEY_DEFAULT_COLOR,
+		};
+	}
+}
+
+/// Sends `VirtualKey` for a key under the mouse cursor on left click
+fn key_click_system(
+	win: Res<Windows>,
+	mouse: Res<Input<MouseButton>>,
+	q: Query<(&Key, &Transform, &Sprite)>,
+	mut key_evw: EventWriter<VirtualKey>,
+) {
+	if !mouse.just_pressed(MouseButton::Left) {
+		return;
+	}
+	let window = win.get_primary().unwrap();
+	let cursor = match window.cursor_position() {
+		Some(position) => position,
+		None => return,
+	};
+	// Window coordinates start at the bottom left corner, camera is centered
+	let point = cursor - Vec2::new(window.width(), window.height()) / 2.0;
+	for (key, tx, sprite) in q.iter() {
+		let half = sprite.custom_size.unwrap_or_default() / 2.0;
+		let offset = point - tx.translation.truncate();
+		if offset.x.abs() <= half.x && offset.y.abs() <= half.y {
+			key_evw.send(VirtualKey(key.0));
+		}
+	}
+}
@@ -1,22 +1,31 @@
 mod components;
 mod consts;
 mod game_plugin;
+mod keyboard_plugin;
 mod no_game_plugin;
 mod resources;
 mod states;
+mod stats;
 mod utils;
 
+use components::TilePosition;
 use game_plugin::GamePlugin;
-use no_game_plugin::{GameOverPlugin, IncorrectWordPlugin, VictoryPlugin};
-use resources::{CurrentGuess, Cursor, Game};
+use keyboard_plugin::KeyboardPlugin;
+use no_game_plugin::{GameOverPlugin, IncorrectWordPlugin, StatsPlugin, VictoryPlugin};
+use resources::{CurrentGuess, Cursor, Game, RejectedGuess};
 use states::GameState;
+use stats::StatsStore;
 
 use bevy::prelude::*;
+use chrono::{Datelike, Local, NaiveDate};
 use clap::{Arg as ClapArg, Command as ClapCommand};
+use lib::database::Database;
+use std::path::PathBuf;
+use std::process;
 
 fn main() {
 	let args = ClapCommand::new("Wordle Game")
-		.arg(ClapArg::new("dict").index(1))
+		.arg(ClapArg::new("dict").index(1).required(true))
 		.after_help("Words dictionary")
 		.arg(
 			ClapArg::new("word")
@@ -26,33 +35,150 @@ fn main() {
 				.takes_value(true)
 				.help("Custom word to play"),
 		)
+		.arg(
+			ClapArg::new("daily")
+				.long("daily")
+				.conflicts_with("word")
+				.help("Plays word of the day, the same for everyone with the same dictionary"),
+		)
+		.arg(
+			ClapArg::new("date")
+				.long("date")
+				.value_name("YYYY-MM-DD")
+				.takes_value(true)
+				.conflicts_with("word")
+				.help("Plays word of given day, implies --daily"),
+		)
+		.arg(
+			ClapArg::new("seed")
+				.long("seed")
+				.value_name("NUMBER")
+				.takes_value(true)
+				.help("Seed for reproducible sequence of words, with --daily gives a different word of the day"),
+		)
+		.arg(
+			ClapArg::new("hard")
+				.long("hard")
+				.help("Hard mode, revealed hints must be used in subsequent guesses"),
+		)
+		.arg(
+			ClapArg::new("save")
+				.long("save")
+				.value_name("FILE")
+				.takes_value(true)
+				.help("Saves the game as JSON after every guess"),
+		)
+		.arg(
+			ClapArg::new("share")
+				.long("share")
+				.value_name("FILE")
+				.takes_value(true)
+				.help("Writes share text of finished games to the file instead of stdout"),
+		)
+		.arg(
+			ClapArg::new("stats")
+				.long("stats")
+				.value_name("FILE")
+				.takes_value(true)
+				.help("File to keep statistics of finished games in [default: stats.json in the user's data directory]"),
+		)
+		.arg(
+			ClapArg::new("resume")
+				.long("resume")
+				.value_name("FILE")
+				.takes_value(true)
+				.conflicts_with_all(&["word", "daily", "date"])
+				.help("Continues game saved by --save"),
+		)
 		.get_matches();
 	let path = args
 		.value_of("dict")
 		.expect("dict file expected as argument");
-	let mut game = Game::new(path);
+	let database = match Database::try_load(path) {
+		Ok(database) => database,
+		Err(err) => {
+			eprintln!("Failed to load dictionary \"{}\": {}", path, err);
+			process::exit(1);
+		}
+	};
+	let seed = match args.value_of("seed").map(str::parse::<u64>) {
+		Some(Ok(seed)) => Some(seed),
+		Some(Err(_)) => {
+			eprintln!("--seed expects a number");
+			process::exit(1);
+		}
+		None => None,
+	};
+	let mut game = Game::new(database, seed);
+	game.set_hard_mode(args.is_present("hard"));
 
 	if let Some(word) = args.value_of("word") {
 		game.reset_with_word(word.to_string())
 	}
-	let cursor = Cursor::default();
+	if args.is_present("daily") || args.is_present("date") {
+		let date = match args.value_of("date") {
+			Some(date) => match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+				Ok(date) => date,
+				Err(err) => {
+					eprintln!("Invalid date \"{}\": {}", date, err);
+					process::exit(1);
+				}
+			},
+			None => Local::now().date_naive(),
+		};
+		let word = match game.get_daily(date.num_days_from_ce() as i64, seed.unwrap_or(0)) {
+			Some(word) => word,
+			None => {
+				eprintln!("Dictionary has no words for the daily puzzle");
+				process::exit(1);
+			}
+		};
+		// Numbered from the day of the first Wordle
+		let puzzle = date
+			.signed_duration_since(NaiveDate::from_ymd_opt(2021, 6, 19).unwrap())
+			.num_days();
+		game.reset_with_puzzle(word, puzzle);
+	}
+	if let Some(path) = args.value_of("resume") {
+		if let Err(err) = game.resume(path) {
+			eprintln!("Failed to resume game \"{}\": {}", path, err);
+			process::exit(1);
+		}
+	}
+	game.set_save_path(args.value_of("save").map(String::from));
+	game.set_share_path(args.value_of("share").map(String::from));
+	let cursor = Cursor {
+		position: TilePosition {
+			row: game.get_attempts(),
+			col: 0,
+		},
+	};
 	let guess = CurrentGuess::default();
+	let stats_path = match args.value_of("stats") {
+		Some(path) => Some(PathBuf::from(path)),
+		None => StatsStore::default_path(),
+	};
+	let stats = StatsStore::load(stats_path);
 	App::new()
 		.insert_resource(Color::rgb(0.15, 0.15, 0.15))
 		.insert_resource(WindowDescriptor {
 			width: 800.0,
-			height: 600.0,
+			height: 800.0,
 			title: "Wordle".to_string(),
 			..Default::default()
 		})
 		.insert_resource(game)
 		.insert_resource(cursor)
 		.insert_resource(guess)
+		.insert_resource(RejectedGuess::default())
+		.insert_resource(stats)
 		.add_plugins(DefaultPlugins)
 		.add_state(GameState::InGame)
 		.add_plugin(GamePlugin)
+		.add_plugin(KeyboardPlugin)
 		.add_plugin(GameOverPlugin)
 		.add_plugin(VictoryPlugin)
 		.add_plugin(IncorrectWordPlugin)
+		.add_plugin(StatsPlugin)
 		.run();
 }
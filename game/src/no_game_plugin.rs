@@ -1,6 +1,8 @@
 use crate::components::*;
 use crate::consts::*;
+use crate::resources::{status_as_colors, Game, RejectedGuess};
 use crate::states::GameState;
+use crate::stats::StatsStore;
 use crate::utils::despawn_screen;
 use bevy::input::keyboard::{KeyCode, KeyboardInput};
 use bevy::input::ElementState;
@@ -20,8 +22,91 @@ impl Plugin for GameOverPlugin {
 	}
 }
 
-fn gameover_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// Shows share text of the finished game below the title and writes it out
+///
+/// The font has no emoji, so the grid is drawn by colored squares.
+fn spawn_share<C: Component + Copy>(
+	commands: &mut Commands,
+	font: Handle<Font>,
+	game: &Game,
+	marker: C,
+) {
+	game.share();
+	let text = game.get_share_text();
+	let header = text.lines().next().unwrap_or_default().to_string();
+	let text_alignment = TextAlignment {
+		vertical: VerticalAlign::Center,
+		horizontal: HorizontalAlign::Center,
+	};
+	commands
+		.spawn_bundle(SpriteBundle {
+			sprite: Sprite {
+				color: BACKGROUND,
+				custom_size: Some(Vec2::new(10000.0, 10000.0)),
+				..Default::default()
+			},
+			transform: Transform {
+				translation: Vec3::new(0.0, 0.0, 1.5),
+				..Default::default()
+			},
+			..Default::default()
+		})
+		.insert(marker);
+	commands
+		.spawn_bundle(Text2dBundle {
+			text: Text::with_section(
+				header,
+				TextStyle {
+					font,
+					font_size: 40.0,
+					color: TILE_DEFAULT_COLOR,
+				},
+				text_alignment,
+			),
+			transform: Transform {
+				translation: Vec3::new(0.0, 120.0, 2.0),
+				..Default::default()
+			},
+			..Default::default()
+		})
+		.insert(marker);
+	let step = SHARE_TILE_SIZE + SHARE_TILE_GAP;
+	for (row, status) in game.get_history().iter().enumerate() {
+		let left = -(status.len() as f32 - 1.0) * step / 2.0;
+		for (col, color) in status_as_colors(status).into_iter().enumerate() {
+			commands
+				.spawn_bundle(SpriteBundle {
+					sprite: Sprite {
+						color,
+						custom_size: Some(Vec2::new(SHARE_TILE_SIZE, SHARE_TILE_SIZE)),
+						..Default::default()
+					},
+					transform: Transform {
+						translation: Vec3::new(
+							left + col as f32 * step,
+							70.0 - row as f32 * step,
+							2.0,
+						),
+						..Default::default()
+					},
+					..Default::default()
+				})
+				.insert(marker);
+		}
+	}
+}
+
+fn gameover_setup(
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+	mut game: ResMut<Game>,
+	mut stats: ResMut<StatsStore>,
+) {
+	if game.mark_recorded() {
+		stats.record(None);
+	}
 	let font_handle: Handle<Font> = asset_server.load(FONT_PATH);
+	spawn_share(&mut commands, font_handle.clone(), &game, GameOverScreen);
 	let text_style = TextStyle {
 		font: font_handle,
 		font_size: 100.0,
@@ -35,7 +120,7 @@ fn gameover_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
 		.spawn_bundle(Text2dBundle {
 			text: Text::with_section("GAME OVER".to_string(), text_style.clone(), text_alignment),
 			transform: Transform {
-				translation: Vec3::new(0.0, 0.0, 2.0),
+				translation: Vec3::new(0.0, 210.0, 2.0),
 				..Default::default()
 			},
 			..Default::default()
@@ -47,6 +132,9 @@ fn gameover_update(mut keys: ResMut<Input<KeyCode>>, mut state: ResMut<State<Gam
 	if keys.just_released(KeyCode::Escape) {
 		state.set(GameState::Restarting).unwrap();
 		keys.reset(KeyCode::Escape);
+	} else if keys.just_released(KeyCode::Return) {
+		state.set(GameState::Stats).unwrap();
+		keys.reset(KeyCode::Return);
 	}
 }
 
@@ -66,8 +154,17 @@ impl Plugin for VictoryPlugin {
 	}
 }
 
-fn victory_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn victory_setup(
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+	mut game: ResMut<Game>,
+	mut stats: ResMut<StatsStore>,
+) {
+	if game.mark_recorded() {
+		stats.record(Some(game.get_attempts()));
+	}
 	let font_handle: Handle<Font> = asset_server.load(FONT_PATH);
+	spawn_share(&mut commands, font_handle.clone(), &game, VictoryScreen);
 	let text_style = TextStyle {
 		font: font_handle,
 		font_size: 100.0,
@@ -81,7 +178,7 @@ fn victory_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
 		.spawn_bundle(Text2dBundle {
 			text: Text::with_section("VICTORY".to_string(), text_style.clone(), text_alignment),
 			transform: Transform {
-				translation: Vec3::new(0.0, 0.0, 2.0),
+				translation: Vec3::new(0.0, 210.0, 2.0),
 				..Default::default()
 			},
 			..Default::default()
@@ -93,11 +190,156 @@ fn victory_update(mut keys: ResMut<Input<KeyCode>>, mut state: ResMut<State<Game
 	if keys.just_released(KeyCode::Escape) {
 		state.set(GameState::Restarting).unwrap();
 		keys.reset(KeyCode::Escape);
+	} else if keys.just_released(KeyCode::Return) {
+		state.set(GameState::Stats).unwrap();
+		keys.reset(KeyCode::Return);
 	}
 }
 
 fn victory_exit(mut commands: Commands) {}
 
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_system_set(SystemSet::on_enter(GameState::Stats).with_system(stats_setup))
+			.add_system_set(SystemSet::on_update(GameState::Stats).with_system(stats_update))
+			.add_system_set(
+				SystemSet::on_exit(GameState::Stats).with_system(despawn_screen::<StatsScreen>),
+			);
+	}
+}
+
+fn spawn_stats_text(
+	commands: &mut Commands,
+	text: String,
+	style: TextStyle,
+	alignment: TextAlignment,
+	position: Vec2,
+) {
+	commands
+		.spawn_bundle(Text2dBundle {
+			text: Text::with_section(text, style, alignment),
+			transform: Transform {
+				translation: position.extend(2.0),
+				..Default::default()
+			},
+			..Default::default()
+		})
+		.insert(StatsScreen);
+}
+
+/// Draws summary and guess distribution bar chart, the last game's bar highlighted
+fn stats_setup(
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+	game: Res<Game>,
+	stats: Res<StatsStore>,
+) {
+	let font_handle: Handle<Font> = asset_server.load(FONT_PATH);
+	let stats = &stats.stats;
+	let text_style = |font_size| TextStyle {
+		font: font_handle.clone(),
+		font_size,
+		color: TILE_DEFAULT_COLOR,
+	};
+	let center = TextAlignment {
+		vertical: VerticalAlign::Center,
+		horizontal: HorizontalAlign::Center,
+	};
+	let left = TextAlignment {
+		vertical: VerticalAlign::Center,
+		horizontal: HorizontalAlign::Left,
+	};
+	spawn_stats_text(
+		&mut commands,
+		"STATISTICS".to_string(),
+		text_style(60.0),
+		center,
+		Vec2::new(0.0, 230.0),
+	);
+	spawn_stats_text(
+		&mut commands,
+		format!(
+			"Played {}  Win {:.0}%  Streak {}  Max {}",
+			stats.played,
+			stats.win_percent(),
+			stats.current_streak,
+			stats.max_streak
+		),
+		text_style(25.0),
+		center,
+		Vec2::new(0.0, 160.0),
+	);
+	let rows = stats.distribution.len().max(game.get_max_attempts());
+	let peak = stats.distribution.iter().copied().max().unwrap_or(0).max(1);
+	let step = STATS_BAR_HEIGHT + 10.0;
+	let bar_left = -STATS_BAR_WIDTH / 2.0;
+	let last_won = if game.is_won() {
+		Some(game.get_attempts())
+	} else {
+		None
+	};
+	for n in 1..=rows {
+		let count = stats.distribution.get(n - 1).copied().unwrap_or(0);
+		let y = 100.0 - (n - 1) as f32 * step;
+		let width = (count as f32 / peak as f32 * STATS_BAR_WIDTH).max(STATS_BAR_HEIGHT);
+		spawn_stats_text(
+			&mut commands,
+			n.to_string(),
+			text_style(30.0),
+			center,
+			Vec2::new(bar_left - 30.0, y),
+		);
+		spawn_stats_text(
+			&mut commands,
+			count.to_string(),
+			text_style(25.0),
+			left,
+			Vec2::new(bar_left + width + 10.0, y),
+		);
+		commands
+			.spawn_bundle(SpriteBundle {
+				sprite: Sprite {
+					color: if last_won == Some(n) {
+						TILE_GREEN_COLOR
+					} else {
+						TILE_GREY_COLOR
+					},
+					custom_size: Some(Vec2::new(width, STATS_BAR_HEIGHT)),
+					..Default::default()
+				},
+				transform: Transform {
+					translation: Vec3::new(bar_left + width / 2.0, y, 2.0),
+					..Default::default()
+				},
+				..Default::default()
+			})
+			.insert(StatsScreen);
+	}
+	commands
+		.spawn_bundle(SpriteBundle {
+			sprite: Sprite {
+				color: BACKGROUND,
+				custom_size: Some(Vec2::new(10000.0, 10000.0)),
+				..Default::default()
+			},
+			transform: Transform {
+				translation: Vec3::new(0.0, 0.0, 1.5),
+				..Default::default()
+			},
+			..Default::default()
+		})
+		.insert(StatsScreen);
+}
+
+fn stats_update(mut keys: ResMut<Input<KeyCode>>, mut state: ResMut<State<GameState>>) {
+	if keys.just_released(KeyCode::Escape) {
+		state.set(GameState::Restarting).unwrap();
+		keys.reset(KeyCode::Escape);
+	}
+}
+
 struct IncorrectWordTimer(Timer);
 const INCORRECTWORD_SEC: f32 = 1.0;
 
@@ -119,11 +361,15 @@ impl Plugin for IncorrectWordPlugin {
 	}
 }
 
-fn incorrectword_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn incorrectword_setup(
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+	rejected: Res<RejectedGuess>,
+) {
 	let font_handle: Handle<Font> = asset_server.load(FONT_PATH);
 	let text_style = TextStyle {
 		font: font_handle,
-		font_size: 80.0,
+		font_size: 50.0,
 		color: Color::RED,
 	};
 	let text_alignment = TextAlignment {
@@ -132,11 +378,7 @@ fn incorrectword_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
 	};
 	commands
 		.spawn_bundle(Text2dBundle {
-			text: Text::with_section(
-				"Incorrect Word".to_string(),
-				text_style.clone(),
-				text_alignment,
-			),
+			text: Text::with_section(rejected.0.clone(), text_style.clone(), text_alignment),
 			transform: Transform {
 				translation: Vec3::new(0.0, 0.0, 2.0),
 				..Default::default()
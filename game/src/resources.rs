@@ -2,78 +2,131 @@ use crate::components::*;
 use crate::consts::*;
 use bevy::prelude::*;
 use lib::database::Database;
-use lib::game::{LetterStatus, WordStatus, Wordle};
-use std::fmt::Debug;
-use std::path::Path;
+use lib::game::{share_text, GuessError, LetterStatus, Validator, WordStatus, Wordle};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::sync::Arc;
 
 /// Enum to express gamestatus
 pub enum GameStatus<T> {
 	Ok(T),
 	InvalidWord,
+	/// Guess doesn't use revealed hints in hard mode, holds the reason
+	HardModeViolation(String),
 	GameOver,
 	Victory(T),
 }
 
+/// Game progress stored by `--save`
+#[derive(Serialize, Deserialize)]
+struct SavedGame {
+	wordle: Wordle,
+	history: Vec<WordStatus>,
+	#[serde(default)]
+	puzzle: Option<i64>,
+	#[serde(default)]
+	recorded: bool,
+}
+
 /// Core Wordle Game struct
 ///
 /// wordle provides the rules api and guess feedback
 /// database can be used for word suggestions etc.
+/// validator accepts only words from the database
+/// rng picks the words, seeded for reproducible games
+/// puzzle is the number of daily puzzle, shown in the share text
+/// history holds accepted guesses, which are saved to `save_path` if set
+/// recorded is set once the finished game is counted in the stats
+/// share text of finished games is written to `share_path`, or stdout if not set
 pub struct Game {
 	wordle: Wordle,
 	database: Database,
+	validator: Validator,
 	pub guesses: Vec<Option<String>>,
 	pub colors: Vec<Vec<Color>>,
 	win: bool,
+	rng: StdRng,
+	puzzle: Option<i64>,
+	history: Vec<WordStatus>,
+	recorded: bool,
+	save_path: Option<String>,
+	share_path: Option<String>,
 }
 
 impl Game {
-	/// Constructor that requires path to database
+	/// Constructor that requires loaded database
+	///
+	/// Words are picked by generator seeded with `seed` if given, randomly otherwise.
 	///
 	/// TODO: change this perhaps to an object with `database` trait or similar
-	pub fn new<P>(filename: P) -> Self
-	where
-		P: AsRef<Path> + Debug,
-	{
-		let database = Database::load(filename);
-		let word = database.get_random();
-		// Sanity check - word lenght
-		assert!(word.len() == 5, "Word lenght is not 5");
+	pub fn new(database: Database, seed: Option<u64>) -> Self {
+		let mut rng = match seed {
+			Some(seed) => StdRng::seed_from_u64(seed),
+			None => StdRng::from_entropy(),
+		};
+		let word = database.get_random(&mut rng);
 		// println!("Wordle game with: {}", word);
-		let wordle = Wordle::new(word);
+		let words = Arc::new(database.get_available().clone());
+		let validator: Validator = Arc::new(move |word: &str| words.contains(word));
+		let mut wordle = Wordle::new(word);
+		wordle.set_validator(validator.clone());
 		let limit = wordle.get_max_attempts();
+		let word_len = database.get_word_len();
 		Self {
 			wordle,
 			database,
+			validator,
 			guesses: vec![None; limit],
-			colors: vec![vec![TILE_DEFAULT_COLOR; 5]; limit],
+			colors: vec![vec![TILE_DEFAULT_COLOR; word_len]; limit],
 			win: false,
+			rng,
+			puzzle: None,
+			history: Vec::new(),
+			recorded: false,
+			save_path: None,
+			share_path: None,
 		}
 	}
 
-	pub fn make_guess_simple(&mut self, word: &String) -> GameStatus<Vec<Color>> {
-		if !self.database.contains(word) {
-			return GameStatus::InvalidWord;
-		}
-		if self.wordle.game_over() {
-			return GameStatus::GameOver;
-		}
-		let status = &self.wordle.guess_word(word);
-		if status.is_correct() {
-			self.win = true;
-			return GameStatus::Victory(status_as_colors(&status));
-		} else {
-			GameStatus::Ok(status_as_colors(&status))
+	pub fn make_guess_simple(&mut self, word: &str) -> GameStatus<Vec<Color>> {
+		match self.wordle.guess_word(word) {
+			Ok(status) => {
+				let colors = status_as_colors(&status);
+				self.guesses[self.history.len()] = Some(word.to_string());
+				self.win = status.is_correct();
+				self.history.push(status);
+				self.autosave();
+				if self.win {
+					GameStatus::Victory(colors)
+				} else {
+					GameStatus::Ok(colors)
+				}
+			}
+			Err(GuessError::GameFinished) => GameStatus::GameOver,
+			Err(GuessError::HardMode(violation)) => {
+				GameStatus::HardModeViolation(violation.to_string())
+			}
+			Err(GuessError::WrongLength { .. }) | Err(GuessError::NotInDictionary(_)) => {
+				GameStatus::InvalidWord
+			}
 		}
 	}
 
 	pub fn reset(&mut self) {
 		self.database.reset();
-		let word = self.database.get_random();
+		let word = self.database.get_random(&mut self.rng);
 		// println!("Wordle game with: {}", word);
-		self.wordle = Wordle::new(word);
-		self.guesses = vec![None; self.wordle.get_max_attempts()];
-		self.colors = vec![vec![TILE_DEFAULT_COLOR; 5]; self.wordle.get_max_attempts()];
-		self.win = false;
+		self.new_wordle(word);
+		self.clear_board();
+	}
+
+	/// Word of the `day`, see `Database::get_daily`
+	pub fn get_daily(&self, day: i64, seed: u64) -> Option<String> {
+		self.database.get_daily(day, seed)
 	}
 
 	pub fn reset_with_word(&mut self, word: String) {
@@ -82,10 +135,174 @@ impl Game {
 			self.database.contains(&word),
 			"Database doesn't contain selected word"
 		);
-		self.wordle = Wordle::new(word);
+		self.new_wordle(word);
+		self.clear_board();
+	}
+
+	/// Empties the board for a new wordle, saving the fresh game
+	fn clear_board(&mut self) {
 		self.guesses = vec![None; self.wordle.get_max_attempts()];
-		self.colors = vec![vec![TILE_DEFAULT_COLOR; 5]; self.wordle.get_max_attempts()];
+		self.colors = vec![vec![TILE_DEFAULT_COLOR; self.word_len()]; self.wordle.get_max_attempts()];
 		self.win = false;
+		self.history.clear();
+		self.recorded = false;
+		self.autosave();
+	}
+
+	/// Saves the game to `path` after every guess and restart
+	pub fn set_save_path(&mut self, path: Option<String>) {
+		self.save_path = path;
+	}
+
+	/// Writes the game to `path` as JSON
+	pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+		let saved = SavedGame {
+			wordle: self.wordle.clone(),
+			history: self.history.clone(),
+			puzzle: self.puzzle,
+			recorded: self.recorded,
+		};
+		let mut writer = BufWriter::new(File::create(path)?);
+		serde_json::to_writer_pretty(&mut writer, &saved)?;
+		writer.flush()?;
+		Ok(())
+	}
+
+	fn autosave(&self) {
+		if let Some(path) = &self.save_path {
+			if let Err(err) = self.save(path) {
+				eprintln!("Failed to save game \"{}\": {}", path, err);
+			}
+		}
+	}
+
+	/// Continues game written by `Game::save`, keeping the current validator
+	///
+	/// Guessed words and their colors are restored to the board.
+	pub fn resume(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+		let reader = BufReader::new(File::open(path)?);
+		let saved: SavedGame = serde_json::from_reader(reader)?;
+		self.wordle = saved.wordle;
+		self.wordle.set_validator(self.validator.clone());
+		self.guesses = vec![None; self.wordle.get_max_attempts()];
+		self.colors = vec![vec![TILE_DEFAULT_COLOR; self.word_len()]; self.wordle.get_max_attempts()];
+		for (row, status) in saved.history.iter().enumerate() {
+			self.guesses[row] = Some(status.get_word());
+			self.colors[row] = status_as_colors(status);
+		}
+		self.win = self.wordle.is_solved();
+		self.history = saved.history;
+		self.puzzle = saved.puzzle;
+		self.recorded = saved.recorded;
+		Ok(())
+	}
+
+	/// Plays daily puzzle number `puzzle` with given word
+	pub fn reset_with_puzzle(&mut self, word: String, puzzle: i64) {
+		self.reset_with_word(word);
+		self.puzzle = Some(puzzle);
+		self.autosave();
+	}
+
+	/// Marks finished game as counted in the stats, `false` if it already was
+	///
+	/// The mark is saved, so a finished game resumed by `--resume` isn't counted again.
+	pub fn mark_recorded(&mut self) -> bool {
+		if self.recorded {
+			return false;
+		}
+		self.recorded = true;
+		self.autosave();
+		true
+	}
+
+	/// Writes share text of finished games to `path` instead of stdout
+	pub fn set_share_path(&mut self, path: Option<String>) {
+		self.share_path = path;
+	}
+
+	/// Result as emoji grid with header, see `lib::game::share_text`
+	pub fn get_share_text(&self) -> String {
+		let title = match self.puzzle {
+			Some(puzzle) => format!("Wordle {}", puzzle),
+			None => "Wordle".to_string(),
+		};
+		share_text(
+			&title,
+			&self.history,
+			self.wordle.get_max_attempts(),
+			self.wordle.is_hard_mode(),
+		)
+	}
+
+	/// Writes share text to the share file or stdout
+	pub fn share(&self) {
+		let text = self.get_share_text();
+		match &self.share_path {
+			Some(path) => {
+				if let Err(err) = std::fs::write(path, text + "\n") {
+					eprintln!("Failed to write share text \"{}\": {}", path, err);
+				}
+			}
+			None => println!("{}", text),
+		}
+	}
+
+	/// Guesses made so far with their feedback
+	pub fn get_history(&self) -> &[WordStatus] {
+		&self.history
+	}
+
+	/// Color of the best status `letter` got in the guesses so far, `None` if not guessed
+	pub fn get_letter_color(&self, letter: char) -> Option<Color> {
+		let mut best: Option<LetterStatus> = None;
+		for status in self.history.iter() {
+			for letter_status in status.iter().filter(|ls| ls.get_letter() == letter) {
+				best = match (best, letter_status) {
+					(Some(LetterStatus::Green(_)), _) => best,
+					(Some(LetterStatus::Yellow(_)), LetterStatus::Grey(_)) => best,
+					_ => Some(*letter_status),
+				};
+			}
+		}
+		best.as_ref().map(letter_color)
+	}
+
+	/// Letters of the dictionary missing on the QWERTY keyboard, e.g. accented ones
+	pub fn get_extra_letters(&self) -> Vec<char> {
+		let mut letters: Vec<char> = self
+			.database
+			.get_available()
+			.iter()
+			.flat_map(|word| word.chars())
+			.filter(|c| !KEY_ROWS.iter().any(|row| row.contains(*c)))
+			.collect();
+		letters.sort_unstable();
+		letters.dedup();
+		letters
+	}
+
+	pub fn get_max_attempts(&self) -> usize {
+		self.wordle.get_max_attempts()
+	}
+
+	/// Number of guesses made, i.e. the row to type into
+	pub fn get_attempts(&self) -> usize {
+		self.history.len()
+	}
+
+	/// Replaces the wordle keeping current hard mode setting and validator
+	fn new_wordle(&mut self, word: String) {
+		let hard_mode = self.wordle.is_hard_mode();
+		self.wordle = Wordle::new(word);
+		self.wordle.set_hard_mode(hard_mode);
+		self.wordle.set_validator(self.validator.clone());
+		self.puzzle = None;
+	}
+
+	/// Turns hard mode on or off, the setting is kept on restarts
+	pub fn set_hard_mode(&mut self, enabled: bool) {
+		self.wordle.set_hard_mode(enabled);
 	}
 
 	pub fn is_lost(&self) -> bool {
@@ -95,21 +312,29 @@ impl Game {
 	pub fn is_won(&self) -> bool {
 		self.win
 	}
+
+	/// Length of the played words, given by the loaded dictionary
+	pub fn word_len(&self) -> usize {
+		self.database.get_word_len()
+	}
 }
 
 pub fn status_as_colors(status: &WordStatus) -> Vec<Color> {
 	let mut colors: Vec<Color> = Vec::new();
 	for ls in status.iter() {
-		let color = match ls {
-			LetterStatus::Grey(_) => TILE_GREY_COLOR,
-			LetterStatus::Yellow(_) => TILE_YELLOW_COLOR,
-			LetterStatus::Green(_) => TILE_GREEN_COLOR,
-		};
-		colors.push(color);
+		colors.push(letter_color(ls));
 	}
 	colors
 }
 
+fn letter_color(letter_status: &LetterStatus) -> Color {
+	match letter_status {
+		LetterStatus::Grey(_) => TILE_GREY_COLOR,
+		LetterStatus::Yellow(_) => TILE_YELLOW_COLOR,
+		LetterStatus::Green(_) => TILE_GREEN_COLOR,
+	}
+}
+
 pub struct Cursor {
 	pub position: TilePosition,
 }
@@ -126,3 +351,12 @@ impl Default for Cursor {
 pub struct CurrentGuess {
 	pub word: String,
 }
+
+/// Reason of the last rejected guess, shown on the incorrect word screen
+pub struct RejectedGuess(pub String);
+
+impl Default for RejectedGuess {
+	fn default() -> Self {
+		Self("Incorrect Word".to_string())
+	}
+}
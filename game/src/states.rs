@@ -5,4 +5,5 @@ pub enum GameState {
 	IncorrectWord,
 	GameOver,
 	Victory,
+	Stats,
 }
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+/// Results of all finished games
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Stats {
+	pub played: usize,
+	pub won: usize,
+	/// Games won in a row, up to the last one
+	pub current_streak: usize,
+	pub max_streak: usize,
+	/// `distribution[n - 1]` is number of games won with `n` guesses
+	pub distribution: Vec<usize>,
+}
+
+impl Stats {
+	/// Counts finished game, won with `guesses` or lost if `None`
+	pub fn record(&mut self, guesses: Option<usize>) {
+		self.played += 1;
+		match guesses {
+			Some(n) => {
+				self.won += 1;
+				self.current_streak += 1;
+				self.max_streak = self.max_streak.max(self.current_streak);
+				if self.distribution.len() < n {
+					self.distribution.resize(n, 0);
+				}
+				self.distribution[n - 1] += 1;
+			}
+			None => self.current_streak = 0,
+		}
+	}
+
+	pub fn win_percent(&self) -> f32 {
+		100.0 * self.won as f32 / self.played.max(1) as f32
+	}
+}
+
+/// Stats resource, saved as JSON to `path` after every finished game
+///
+/// Without a path, e.g. no data directory is known or the file couldn't be read,
+/// stats last until exit.
+pub struct StatsStore {
+	pub stats: Stats,
+	path: Option<PathBuf>,
+}
+
+impl StatsStore {
+	/// `wordle/stats.json` in the user's data directory
+	pub fn default_path() -> Option<PathBuf> {
+		dirs::data_dir().map(|dir| dir.join("wordle").join("stats.json"))
+	}
+
+	/// Reads stats from `path`, starts from scratch if the file doesn't exist
+	///
+	/// A file that can't be read is reported and left untouched, stats are
+	/// then not saved so that the history in it isn't lost.
+	pub fn load(path: Option<PathBuf>) -> Self {
+		let file = match path {
+			Some(path) if path.exists() => path,
+			path => {
+				return Self {
+					stats: Stats::default(),
+					path,
+				}
+			}
+		};
+		let loaded = fs::read_to_string(&file)
+			.map_err(|err| err.to_string())
+			.and_then(|text| serde_json::from_str(&text).map_err(|err| err.to_string()));
+		match loaded {
+			Ok(stats) => Self {
+				stats,
+				path: Some(file),
+			},
+			Err(err) => {
+				eprintln!(
+					"Failed to load stats \"{}\": {}, results of this session won't be saved",
+					file.display(),
+					err
+				);
+				Self {
+					stats: Stats::default(),
+					path: None,
+				}
+			}
+		}
+	}
+
+	/// Counts finished game and saves the stats, see `Stats::record`
+	pub fn record(&mut self, guesses: Option<usize>) {
+		self.stats.record(guesses);
+		if let Err(err) = self.save() {
+			eprintln!("Failed to save stats: {}", err);
+		}
+	}
+
+	fn save(&self) -> Result<(), Box<dyn Error>> {
+		let path = match &self.path {
+			Some(path) => path,
+			None => return Ok(()),
+		};
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir)?;
+		}
+		fs::write(path, serde_json::to_string_pretty(&self.stats)?)?;
+		Ok(())
+	}
+}
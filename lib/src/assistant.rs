@@ -1,85 +1,592 @@
-use crate::database::Database;
-use crate::game::{LetterStatus, WordStatus};
+use crate::constraints::Constraints;
+use crate::database::{check_pools, Database, DatabaseError};
+use crate::game::{HardModeRules, StatusError, WordStatus};
+use crate::letter_frequency::LetterFrequencyMap;
+use crate::pattern::{Feedback, PatternMatrix};
 use crate::suggestion::*;
+use crate::tree::{DecisionTree, TreeError};
+use indexmap::IndexSet;
+use rand::rngs::StdRng;
+use rand::{thread_rng, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
 use std::fmt::Debug;
 use std::path::Path;
+use std::sync::Arc;
 
 const MAX_SUGGESTIONS: usize = 25;
+const MAX_PROBES: usize = 10;
 const COL_WIDTH: usize = 14;
+const FREQ_COL_WIDTH: usize = 22;
 
+/// Part of the assistant state worth saving, see `Assistant::get_state`
+///
+/// Candidates and everything else is recomputed from the history on restore.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AssistantState {
+	pub history: Vec<(String, WordStatus)>,
+	pub hard_mode: bool,
+}
+
+/// Reason why `Assistant::try_update` refused a guess
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateError {
+	/// Guess is in neither the solution nor the guess pool
+	UnknownWord(String),
+	/// Status string doesn't fit the guess
+	Status(StatusError),
+}
+
+impl fmt::Display for UpdateError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::UnknownWord(word) => write!(f, "\"{}\" not found in dictionary", word),
+			Self::Status(err) => write!(f, "{}", err),
+		}
+	}
+}
+
+impl Error for UpdateError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::Status(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+impl From<StatusError> for UpdateError {
+	fn from(err: StatusError) -> Self {
+		Self::Status(err)
+	}
+}
+
+/// Candidates and everything derived from them, see `Assistant::checkpoint`
+#[derive(Clone)]
+pub struct Checkpoint {
+	database: Database,
+	suggestions: SuggestionCollection,
+	hard_mode: Option<HardModeRules>,
+	history_len: usize,
+	constraints: Constraints,
+	frequencies: LetterFrequencyMap,
+}
+
+/// Assistant suggesting next guesses
+///
+/// `database` holds the solution pool, words that can still be the answer are `available`.
+/// Optional `guesses` holds the allowed guess pool, from which probe words are ranked
+/// by expected information against the remaining solutions. Probes and suggestions
+/// order are computed lazily as it is expensive for large pools.
+///
+/// In hard mode only probes respecting revealed hints are suggested, remaining
+/// candidates respect them by definition.
+///
+/// Every applied guess is kept in `history`, so updates can be undone
+/// by replaying the remaining entries from scratch or rolled back to a `Checkpoint`. What the guesses revealed
+/// is accumulated in `constraints`, which filter the candidates.
+/// Letter `frequencies` used to score words are recomputed from the candidates
+/// after every update.
+///
+/// With a decision `tree` loaded the best guess is looked up in it as long as
+/// the played guesses follow the tree, falling back to `sort_mode` otherwise.
+///
+/// Optional `patterns` matrix turns the feedback computations of entropy
+/// and minimax scoring into lookups.
+///
+/// Random picks use `rng` if seeded, `thread_rng` otherwise.
+#[derive(Default)]
 pub struct Assistant {
 	database: Database,
+	guesses: Option<Database>,
 	suggestions: SuggestionCollection,
+	probes: Option<SuggestionCollection>,
+	sort_mode: SortMode,
+	sorted: bool,
+	hard_mode: Option<HardModeRules>,
+	history: Vec<(String, WordStatus)>,
+	constraints: Constraints,
+	tree: Option<DecisionTree>,
+	patterns: Option<Arc<PatternMatrix>>,
+	frequencies: LetterFrequencyMap,
+	rng: Option<StdRng>,
 }
 
 impl Assistant {
 	/// Constructor
+	///
+	/// Panics if the dictionary can't be loaded, see `Assistant::try_new`
 	pub fn new<P>(filename: P) -> Self
 	where
 		P: AsRef<Path> + Debug,
 	{
 		let database = Database::load(filename);
-		let suggestions = SuggestionCollection::from(database.get_available());
+		Self::from_database(database)
+	}
+
+	/// Fallible constructor
+	pub fn try_new<P>(filename: P) -> Result<Self, DatabaseError>
+	where
+		P: AsRef<Path> + Debug,
+	{
+		let database = Database::try_load(filename)?;
+		Ok(Self::from_database(database))
+	}
+
+	/// Constructor with separate solution and allowed guess pools
+	///
+	/// The guess pool is used to suggest probe words, those don't have to
+	/// be possible solutions but may narrow down the candidates better.
+	///
+	/// Panics if either dictionary can't be loaded, see `Database::try_load` and `Assistant::from_databases`
+	pub fn with_guesses<P, Q>(solutions: P, guesses: Q) -> Self
+	where
+		P: AsRef<Path> + Debug,
+		Q: AsRef<Path> + Debug,
+	{
+		Self::from_databases(Database::load(solutions), Some(Database::load(guesses)))
+	}
+
+	/// Constructor from already loaded solution and optional guess pool
+	///
+	/// Panics if words in the pools are of different length, see `Assistant::try_from_databases`
+	pub fn from_databases(solutions: Database, guesses: Option<Database>) -> Self {
+		match Self::try_from_databases(solutions, guesses) {
+			Ok(assistant) => assistant,
+			Err(err) => panic!("Invalid guess pool: {}", err),
+		}
+	}
+
+	/// Fallible constructor from already loaded pools
+	///
+	/// # Example
+	/// ```
+	/// use lib::assistant::Assistant;
+	/// use lib::database::{Database, DatabaseError};
+	///
+	/// let solutions = Database::load("../data/test_dict.txt");
+	/// let guesses = Database::load("../data/test_dict_11.txt");
+	/// let result = Assistant::try_from_databases(solutions, Some(guesses));
+	/// assert!(matches!(result, Err(DatabaseError::PoolLength { .. })));
+	/// ```
+	pub fn try_from_databases(
+		solutions: Database,
+		guesses: Option<Database>,
+	) -> Result<Self, DatabaseError> {
+		if let Some(guesses) = &guesses {
+			check_pools(&solutions, guesses)?;
+		}
+		Ok(Self {
+			guesses,
+			..Self::from_database(solutions)
+		})
+	}
+
+	fn from_database(database: Database) -> Self {
+		let frequencies = LetterFrequencyMap::from_words(database.get_available());
+		let suggestions = SuggestionCollection::from_words(database.get_available(), &frequencies);
 		Self {
 			database,
 			suggestions,
+			frequencies,
+			..Default::default()
+		}
+	}
+
+	/// Sort mode setter, current suggestions get reordered accordingly
+	pub fn set_sort_mode(&mut self, mode: SortMode) {
+		self.sort_mode = mode;
+		self.sorted = false;
+		self.probes = None;
+	}
+
+	/// Sort mode getter
+	pub fn get_sort_mode(&self) -> SortMode {
+		self.sort_mode
+	}
+
+	/// Turns hard mode on or off, see `Wordle::set_hard_mode`
+	pub fn set_hard_mode(&mut self, enabled: bool) {
+		self.hard_mode = if enabled {
+			Some(self.hard_mode.take().unwrap_or_default())
+		} else {
+			None
+		};
+		self.probes = None;
+	}
+
+	/// Checks if hard mode is on
+	pub fn is_hard_mode(&self) -> bool {
+		self.hard_mode.is_some()
+	}
+
+	/// Sets decision tree used by `Assistant::get_best`, `None` turns it off
+	pub fn set_tree(&mut self, tree: Option<DecisionTree>) {
+		self.tree = tree;
+	}
+
+	/// Loads decision tree written by `DecisionTree::save`, see `Assistant::set_tree`
+	pub fn load_tree<P>(&mut self, filename: P) -> Result<(), TreeError>
+	where
+		P: AsRef<Path> + Debug,
+	{
+		self.tree = Some(DecisionTree::load(filename)?);
+		Ok(())
+	}
+
+	/// Guess looked up in the decision tree, `None` if no tree is loaded
+	/// or the history left it
+	pub fn get_tree_guess(&self) -> Option<&str> {
+		self.tree.as_ref()?.get_guess(&self.history)
+	}
+
+	/// Precomputes feedback of all solutions and guesses against all solutions
+	///
+	/// Takes a while for large pools, but scoring is then much faster.
+	/// Returns the matrix so it can be shared, see `Assistant::set_patterns`.
+	pub fn precompute_patterns(&mut self) -> Arc<PatternMatrix> {
+		let mut solutions = self.database.clone();
+		solutions.reset();
+		let mut guesses = solutions.get_available().clone();
+		if let Some(pool) = &self.guesses {
+			let mut pool = pool.clone();
+			pool.reset();
+			guesses.extend(pool.get_available().iter().cloned());
 		}
+		let matrix = Arc::new(PatternMatrix::new(&guesses, solutions.get_available()));
+		self.patterns = Some(matrix.clone());
+		matrix
+	}
+
+	/// Seeds the random generator, so random picks are reproducible
+	pub fn set_seed(&mut self, seed: u64) {
+		self.rng = Some(StdRng::seed_from_u64(seed));
 	}
 
-	/// Displays viable solutions
-	pub fn display_suggestions(&self) {
+	/// Sets precomputed feedback matrix, `None` computes feedback on the fly
+	pub fn set_patterns(&mut self, patterns: Option<Arc<PatternMatrix>>) {
+		self.patterns = patterns;
+	}
+
+	/// Length of words the assistant works with
+	pub fn get_word_len(&self) -> usize {
+		self.database.get_word_len()
+	}
+
+	/// Viable solutions ordered by the sort mode
+	pub fn get_suggestions(&mut self) -> &[Suggestion] {
+		self.sort_suggestions();
+		&self.suggestions.items
+	}
+
+	/// Displays viable solutions, followed by best probe words if guess pool is loaded
+	pub fn display_suggestions(&mut self) {
+		self.display_top_suggestions(MAX_SUGGESTIONS);
+	}
+
+	/// Same as `Assistant::display_suggestions` showing up to `count` solutions
+	pub fn display_top_suggestions(&mut self, count: usize) {
+		if let Some(guess) = self.get_tree_guess() {
+			println!("Decision tree suggests: {}\n", guess);
+		}
+		self.sort_suggestions();
 		let len = self.database.get_len();
-		let limit = len.min(MAX_SUGGESTIONS);
+		let limit = len.min(count);
 		println!("Showing {} out of {} suggestions", limit, len);
 		println!(
-			"{:<width$}{:<width$}{}",
+			"{:<width$}{:<width$}{:<freq_width$}{:<freq_width$}{:<freq_width$}Worst case",
 			"Suggestion",
 			"Unique chars",
 			"Avg. frequency score",
-			width = COL_WIDTH
+			"Positional score",
+			"Entropy (bits)",
+			width = COL_WIDTH,
+			freq_width = FREQ_COL_WIDTH
 		);
-		let limit = MAX_SUGGESTIONS.min(self.suggestions.items.len());
+		let limit = count.min(self.suggestions.items.len());
 		for i in 0..limit {
-			let i = i as usize;
 			self.suggestions.items[i].display();
 		}
+		if self.guesses.is_none() || self.database.get_len() <= 2 {
+			return;
+		}
+		let probes = self.get_probes();
+		let limit = MAX_PROBES.min(probes.items.len());
+		println!("\nBest {} probe words", limit);
+		println!(
+			"{:<width$}{:<width$}{:<freq_width$}{:<freq_width$}{:<freq_width$}Worst case",
+			"Probe",
+			"Unique chars",
+			"Avg. frequency score",
+			"Positional score",
+			"Entropy (bits)",
+			width = COL_WIDTH,
+			freq_width = FREQ_COL_WIDTH
+		);
+		for probe in probes.items.iter().take(limit) {
+			probe.display();
+		}
+	}
+
+	/// Narrows down the candidates by guessed `input` and game's response `status_string`
+	///
+	/// Invalid guesses are silently ignored, see `Assistant::try_update`
+	#[deprecated(note = "use `Assistant::try_update` and handle the error")]
+	pub fn update(&mut self, input: &str, status_string: &str) {
+		let _ = self.try_update(input, status_string);
+	}
+
+	/// Narrows down the candidates by guessed `input` and game's response `status_string`,
+	/// returns why the guess was refused
+	pub fn try_update(&mut self, input: &str, status_string: &str) -> Result<(), UpdateError> {
+		if !self.is_known(input) {
+			return Err(UpdateError::UnknownWord(input.to_string()));
+		}
+		let wordstatus = WordStatus::try_from_strings(input, status_string)?;
+		self.apply(input, &wordstatus);
+		self.history.push((input.to_string(), wordstatus));
+		Ok(())
+	}
+
+	/// Words that can still be the answer
+	pub fn get_candidates(&self) -> &IndexSet<String> {
+		self.database.get_available()
+	}
+
+	/// Applied guesses with their statuses, oldest first
+	pub fn get_history(&self) -> &[(String, WordStatus)] {
+		&self.history
+	}
+
+	/// Everything known about the hidden word from applied guesses
+	pub fn get_constraints(&self) -> &Constraints {
+		&self.constraints
 	}
 
-	pub fn update(&mut self, input: &String, status_string: &String) {
+	/// State to be saved and later passed to `Assistant::restore`
+	pub fn get_state(&self) -> AssistantState {
+		AssistantState {
+			history: self.history.clone(),
+			hard_mode: self.is_hard_mode(),
+		}
+	}
+
+	/// Starts over from given `state`, replaying its history
+	///
+	/// Entries with words not found in the dictionaries are skipped.
+	/// Returns number of entries restored.
+	pub fn restore(&mut self, state: &AssistantState) -> usize {
+		self.set_hard_mode(state.hard_mode);
+		self.reset();
+		for (word, wordstatus) in state.history.iter() {
+			if self.is_known(word) {
+				self.apply(word, wordstatus);
+				self.history.push((word.clone(), wordstatus.clone()));
+			}
+		}
+		self.history.len()
+	}
+
+	/// Reverts last `n` updates, recomputing candidates from the remaining history
+	///
+	/// Returns number of updates actually reverted.
+	pub fn undo(&mut self, n: usize) -> usize {
+		let n = n.min(self.history.len());
+		let mut history = std::mem::take(&mut self.history);
+		history.truncate(history.len() - n);
+		self.reset();
+		for (word, wordstatus) in history.iter() {
+			self.apply(word, wordstatus);
+		}
+		self.history = history;
+		n
+	}
+
+	/// Current candidates to go back to by `Assistant::rollback`
+	///
+	/// Cheaper than `Assistant::undo`, which replays the whole history.
+	pub fn checkpoint(&self) -> Checkpoint {
+		Checkpoint {
+			database: self.database.clone(),
+			suggestions: self.suggestions.clone(),
+			hard_mode: self.hard_mode.clone(),
+			history_len: self.history.len(),
+			constraints: self.constraints.clone(),
+			frequencies: self.frequencies.clone(),
+		}
+	}
+
+	/// Reverts all updates made after `checkpoint` was taken
+	///
+	/// # Example
+	/// ```
+	/// use lib::assistant::Assistant;
+	///
+	/// let mut assistant = Assistant::new("../data/test_dict.txt");
+	/// let checkpoint = assistant.checkpoint();
+	/// let candidates = assistant.get_candidates().clone();
+	/// assistant.try_update("bbbcb", "XXXGX").unwrap();
+	/// assert!(assistant.get_candidates().len() < candidates.len());
+	/// assistant.rollback(&checkpoint);
+	/// assert!(assistant.get_history().is_empty());
+	/// assert!(assistant.get_candidates().iter().eq(candidates.iter()));
+	/// ```
+	pub fn rollback(&mut self, checkpoint: &Checkpoint) {
+		self.database = checkpoint.database.clone();
+		self.suggestions = checkpoint.suggestions.clone();
+		self.hard_mode = checkpoint.hard_mode.clone();
+		self.history.truncate(checkpoint.history_len);
+		self.constraints = checkpoint.constraints.clone();
+		self.frequencies = checkpoint.frequencies.clone();
+		self.sorted = false;
+		self.probes = None;
+	}
+
+	/// Prunes the candidates by given guess without recording it in history
+	fn apply(&mut self, input: &str, wordstatus: &WordStatus) {
 		if self.database.available_contains(input) {
 			self.database.discard(input);
-		} else {
-			println!("Word not found in available");
-			return;
 		}
-		let wordstatus = WordStatus::from_strings(input, status_string);
-		for (pos, ls) in wordstatus.iter().enumerate() {
-			match ls {
-				LetterStatus::Green(letter) => self.database.prune_green(*letter, pos),
-				LetterStatus::Yellow(letter) => {
-					let cnt = wordstatus.char_count(*letter);
-					self.database.prune_n_yellow(*letter, pos, cnt.0 + cnt.1);
-				}
-				LetterStatus::Grey(letter) => {
-					let cnt = wordstatus.char_count(*letter);
-					self.database.prune_n_grey(*letter, cnt.0 + cnt.1);
-				}
-			}
+		if let Some(rules) = self.hard_mode.as_mut() {
+			rules.update(wordstatus);
 		}
+		self.constraints.add(wordstatus);
+		self.database.prune(&self.constraints);
 		let available = self.database.get_available();
-		self.suggestions = SuggestionCollection::from(available);
+		self.frequencies = LetterFrequencyMap::from_words(available);
+		self.suggestions = SuggestionCollection::from_words(available, &self.frequencies);
 		// Update the unique counter by updating with current guess' greens and yellows
 		// Greens string is appended to the actual string then uniques are counted
 		// this is a dirty way to create duplicates threfore reduce unique letters
 		// TODO: add previous guesses
 		let mut greens_and_yellows = wordstatus.get_green_chars();
-		greens_and_yellows.extend(wordstatus.get_yellow_chars().chars());
-		self.suggestions.update_with_info(&greens_and_yellows);
-		self.suggestions.sort_suggestions_freq();
+		greens_and_yellows.push_str(&wordstatus.get_yellow_chars());
+		self.suggestions
+			.update_with_info(&greens_and_yellows, &self.frequencies);
+		self.sorted = false;
+		self.probes = None;
+	}
+
+	/// Returns all discarded words back to the candidates and clears history, starting over
+	pub fn reset(&mut self) {
+		self.history.clear();
+		self.constraints = Constraints::new();
+		self.database.reset();
+		self.frequencies = LetterFrequencyMap::from_words(self.database.get_available());
+		self.suggestions =
+			SuggestionCollection::from_words(self.database.get_available(), &self.frequencies);
+		if let Some(rules) = self.hard_mode.as_mut() {
+			*rules = HardModeRules::new();
+		}
+		self.sorted = false;
+		self.probes = None;
+	}
+
+	/// Checks if `word` is in either solution or guess pool
+	pub fn is_known(&self, word: &str) -> bool {
+		self.database.contains(word)
+			|| self
+				.guesses
+				.as_ref()
+				.is_some_and(|guesses| guesses.contains(word))
+	}
+
+	/// Returns probe words ranked by expected information against available solutions,
+	/// or by worst case in minimax mode.
+	///
+	/// Computed on first call after each update or sort mode change. Empty if no guess pool is loaded.
+	/// In hard mode words not respecting revealed hints are left out.
+	fn get_probes(&mut self) -> &SuggestionCollection {
+		if self.probes.is_none() {
+			let mut probes = match &self.guesses {
+				Some(guesses) => {
+					SuggestionCollection::from_words(guesses.get_available(), &self.frequencies)
+				}
+				None => SuggestionCollection::new(),
+			};
+			if let Some(rules) = &self.hard_mode {
+				probes.items.retain(|probe| rules.check(probe.get_word()).is_ok());
+			}
+			let feedback = Feedback::new(self.database.get_available(), self.patterns.as_deref());
+			if self.sort_mode == SortMode::Minimax {
+				probes.update_worst_case(&feedback);
+				probes.sort_suggestions_minimax();
+			} else {
+				probes.update_entropy(&feedback);
+				probes.sort_suggestions_entropy();
+			}
+			self.probes = Some(probes);
+		}
+		self.probes.as_ref().unwrap()
+	}
+
+	/// Sorts suggestions by current `sort_mode` unless already sorted
+	///
+	/// Entropy and worst case are computed only when needed as those are
+	/// quadratic in number of available words.
+	fn sort_suggestions(&mut self) {
+		if self.sorted {
+			return;
+		}
+		let available = self.database.get_available();
+		match self.sort_mode {
+			SortMode::Entropy => self
+				.suggestions
+				.update_entropy(&Feedback::new(available, self.patterns.as_deref())),
+			SortMode::Minimax => self
+				.suggestions
+				.update_worst_case(&Feedback::new(available, self.patterns.as_deref())),
+			_ => {}
+		}
+		self.suggestions.sort_by_mode(self.sort_mode);
+		self.sorted = true;
 	}
 
+	/// Up to `count` guesses to try, `Assistant::get_best` first, then best
+	/// candidates and probe words, about half each, in order of current `sort_mode`
+	///
+	/// # Example
+	/// ```
+	/// use lib::assistant::Assistant;
+	///
+	/// let mut assistant = Assistant::new("../data/test_dict.txt");
+	/// let ranked = assistant.get_ranked(3);
+	/// assert_eq!(ranked.len(), 3);
+	/// assert_eq!(Some(ranked[0].clone()), assistant.get_best());
+	/// ```
+	pub fn get_ranked(&mut self, count: usize) -> Vec<String> {
+		let mut ranked: IndexSet<String> = self.get_best().into_iter().collect();
+		self.sort_suggestions();
+		let candidates: Vec<String> = self
+			.suggestions
+			.items
+			.iter()
+			.take(count)
+			.map(|s| s.get_word_clone())
+			.collect();
+		ranked.extend(candidates.iter().take(count / 2).cloned());
+		if self.guesses.is_some() {
+			let probes = self.get_probes().items.iter();
+			let probes = probes.filter(|probe| !ranked.contains(probe.get_word()));
+			let missing = count.saturating_sub(ranked.len());
+			let probes: Vec<String> = probes.take(missing).map(|s| s.get_word_clone()).collect();
+			ranked.extend(probes);
+		}
+		ranked.extend(candidates);
+		ranked.into_iter().take(count).collect()
+	}
+
+	/// Random word of the suggestions with most unique chars, see `Assistant::set_seed`
 	pub fn get_random(&mut self) -> Option<String> {
-		self.suggestions.get_random_most_unique()
+		// reorders suggestions by unique chars
+		self.sorted = false;
+		match &mut self.rng {
+			Some(rng) => self.suggestions.get_random_most_unique(rng),
+			None => self.suggestions.get_random_most_unique(&mut thread_rng()),
+		}
 	}
 
 	pub fn suggestions_empty(&self) -> bool {
@@ -87,15 +594,57 @@ impl Assistant {
 	}
 
 	pub fn get_most_freq_uniq(&mut self) -> Option<String> {
+		// reorders suggestions by unique chars
+		self.sorted = false;
 		self.suggestions.get_most_freq_uniq()
 	}
-}
 
-impl Default for Assistant {
-	fn default() -> Self {
-		Self {
-			database: Database::new(),
-			suggestions: SuggestionCollection::new(),
+	/// Returns the best suggestion according to current `sort_mode`
+	///
+	/// Unique mode picks the most frequent word among the ones with most unique letters.
+	/// Frequency and positional modes pick the word with highest respective score.
+	/// Entropy mode picks a probe word from the guess pool if it carries
+	/// more information than the best remaining candidate.
+	/// Minimax mode picks a probe word if it leaves fewer candidates in the worst case
+	/// than the best remaining candidate.
+	///
+	/// Decision tree, if loaded, takes precedence over all of the modes.
+	pub fn get_best(&mut self) -> Option<String> {
+		if let Some(guess) = self.get_tree_guess() {
+			return Some(guess.to_string());
+		}
+		match self.sort_mode {
+			SortMode::Unique => self.get_most_freq_uniq(),
+			SortMode::Frequency | SortMode::Positional => {
+				self.sort_suggestions();
+				self.suggestions.items.first().map(|s| s.get_word_clone())
+			}
+			SortMode::Entropy => {
+				self.sort_suggestions();
+				let candidate = self.suggestions.items.first()?.clone();
+				if self.guesses.is_none() {
+					return Some(candidate.get_word_clone());
+				}
+				match self.get_probes().items.first() {
+					Some(probe) if probe.get_entropy() > candidate.get_entropy() => {
+						Some(probe.get_word_clone())
+					}
+					_ => Some(candidate.get_word_clone()),
+				}
+			}
+			SortMode::Minimax => {
+				self.sort_suggestions();
+				let candidate = self.suggestions.items.first()?.clone();
+				if self.guesses.is_none() {
+					return Some(candidate.get_word_clone());
+				}
+				match self.get_probes().items.first() {
+					Some(probe) if probe.get_worst_case() < candidate.get_worst_case() => {
+						Some(probe.get_word_clone())
+					}
+					_ => Some(candidate.get_word_clone()),
+				}
+			}
 		}
 	}
 }
@@ -0,0 +1,207 @@
+//! Constraint model of the hidden word
+//!
+//! Accumulates everything known about the hidden word from any number
+//! of guesses and their statuses, so candidates can be filtered in a single pass.
+
+use crate::game::{LetterStatus, WordStatus};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// What is known about a single position of the hidden word
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PositionConstraint {
+	/// Letter known to be at this position
+	pub green: Option<char>,
+	/// Letters known not to be at this position
+	pub excluded: BTreeSet<char>,
+}
+
+impl PositionConstraint {
+	/// Checks if `letter` is allowed at this position
+	pub fn allows(&self, letter: char) -> bool {
+		self.green.is_none_or(|green| green == letter) && !self.excluded.contains(&letter)
+	}
+}
+
+/// Known bounds on number of occurrences of a letter in the hidden word
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LetterCount {
+	pub min: usize,
+	/// `None` if the upper bound is not known
+	pub max: Option<usize>,
+}
+
+impl LetterCount {
+	/// Checks if `count` is within the bounds
+	pub fn allows(&self, count: usize) -> bool {
+		count >= self.min && self.max.is_none_or(|max| count <= max)
+	}
+}
+
+impl fmt::Display for LetterCount {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.max {
+			Some(max) if max == self.min => write!(f, "={}", max),
+			Some(max) => write!(f, "={}..{}", self.min, max),
+			None => write!(f, ">={}", self.min),
+		}
+	}
+}
+
+/// Constraints on the hidden word
+///
+/// Per position allowed letters and per letter min/max counts.
+///
+/// # Example
+/// ```
+/// use lib::constraints::Constraints;
+/// use lib::game::WordStatus;
+///
+/// let mut constraints = Constraints::new();
+/// constraints.add(&WordStatus::from_strings("crane", "gyxxy"));
+/// assert!(constraints.matches("cover"));
+/// assert!(!constraints.matches("crane"));
+/// assert_eq!(constraints.to_string(), "c[^r][^a][^n][^e] a=0 c>=1 e>=1 n=0 r>=1");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Constraints {
+	positions: Vec<PositionConstraint>,
+	counts: BTreeMap<char, LetterCount>,
+}
+
+impl Constraints {
+	/// Constructor, no constraints
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Builds constraints from sequence of statuses
+	pub fn from_statuses<'a, I>(statuses: I) -> Self
+	where
+		I: IntoIterator<Item = &'a WordStatus>,
+	{
+		let mut constraints = Self::new();
+		for status in statuses {
+			constraints.add(status);
+		}
+		constraints
+	}
+
+	/// Checks if nothing is known yet
+	pub fn is_empty(&self) -> bool {
+		self.positions.is_empty() && self.counts.is_empty()
+	}
+
+	/// Per position constraints, empty if nothing is known yet
+	pub fn get_positions(&self) -> &[PositionConstraint] {
+		&self.positions
+	}
+
+	/// Count bounds of `letter`, `None` if nothing is known about it
+	pub fn get_count(&self, letter: char) -> Option<&LetterCount> {
+		self.counts.get(&letter)
+	}
+
+	/// Accumulates what `status` reveals about the hidden word
+	///
+	/// Green letters fix their position, yellow and grey letters are excluded
+	/// from their position. Greens and yellows of a letter give its minimal count,
+	/// a grey instance of the letter makes that count exact.
+	pub fn add(&mut self, status: &WordStatus) {
+		if self.positions.len() < status.len() {
+			self.positions.resize(status.len(), PositionConstraint::default());
+		}
+		let mut revealed = Self::new();
+		revealed.positions.resize(status.len(), PositionConstraint::default());
+		for (position, letter_status) in status.iter().enumerate() {
+			let letter = match letter_status {
+				LetterStatus::Green(c) => {
+					revealed.positions[position].green = Some(*c);
+					*c
+				}
+				LetterStatus::Yellow(c) | LetterStatus::Grey(c) => {
+					revealed.positions[position].excluded.insert(*c);
+					*c
+				}
+			};
+			let (green, yellow, grey) = status.char_count(letter);
+			let min = green + yellow;
+			let max = if grey > 0 { Some(min) } else { None };
+			revealed.counts.insert(letter, LetterCount { min, max });
+		}
+		self.merge(&revealed);
+	}
+
+	/// Combines with `other`, the result allows only words allowed by both
+	pub fn merge(&mut self, other: &Constraints) {
+		if self.positions.len() < other.positions.len() {
+			self.positions
+				.resize(other.positions.len(), PositionConstraint::default());
+		}
+		for (mine, theirs) in self.positions.iter_mut().zip(other.positions.iter()) {
+			match (mine.green, theirs.green) {
+				(None, green) => mine.green = green,
+				// Contradicting greens, nothing can match
+				(Some(a), Some(b)) if a != b => {
+					mine.excluded.insert(a);
+					mine.excluded.insert(b);
+				}
+				_ => {}
+			}
+			mine.excluded.extend(theirs.excluded.iter());
+		}
+		for (letter, theirs) in other.counts.iter() {
+			let count = self.counts.entry(*letter).or_insert(*theirs);
+			count.min = count.min.max(theirs.min);
+			count.max = match (count.max, theirs.max) {
+				(Some(a), Some(b)) => Some(a.min(b)),
+				(a, b) => a.or(b),
+			};
+		}
+	}
+
+	/// Checks if `word` can be the hidden word
+	pub fn matches(&self, word: &str) -> bool {
+		let mut length = 0;
+		for (position, letter) in word.chars().enumerate() {
+			if let Some(constraint) = self.positions.get(position) {
+				if !constraint.allows(letter) {
+					return false;
+				}
+			}
+			length += 1;
+		}
+		if !self.positions.is_empty() && length != self.positions.len() {
+			return false;
+		}
+		self.counts
+			.iter()
+			.all(|(letter, count)| count.allows(word.matches(*letter).count()))
+	}
+}
+
+impl fmt::Display for Constraints {
+	/// Regex like pattern of positions followed by letter counts,
+	/// e.g. `c[^r][^a][^n][^e] a=0 c>=1 e>=1 n=0 r>=1`
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for position in self.positions.iter() {
+			match position.green {
+				Some(green) => write!(f, "{}", green)?,
+				None if position.excluded.is_empty() => write!(f, ".")?,
+				None => {
+					let excluded: String = position.excluded.iter().collect();
+					write!(f, "[^{}]", excluded)?
+				}
+			}
+		}
+		for (letter, count) in self.counts.iter() {
+			write!(f, " {}{}", letter, count)?;
+		}
+		Ok(())
+	}
+}
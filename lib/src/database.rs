@@ -1,12 +1,182 @@
 //! Word database module
 //!
 
+use crate::constraints::Constraints;
+use crate::pattern::Pattern;
 use indexmap::IndexSet;
-use rand::{thread_rng, Rng};
+use rand::Rng;
+use std::error::Error;
+use std::fmt;
 use std::fmt::Debug;
 use std::fs::File;
+use std::io;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
+use std::sync::Arc;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Errors that can occur while loading the database
+///
+/// Line numbers are 1-based, as shown by text editors.
+#[derive(Debug)]
+pub enum DatabaseError {
+	/// File could not be opened or read
+	Io(io::Error),
+	/// File contains no words
+	Empty,
+	/// Word at `line` has different length than the first word in the file
+	MixedLength {
+		line: usize,
+		expected: usize,
+		found: usize,
+	},
+	/// Word at `line` contains other than alphabetic characters
+	NonAlphabetic { line: usize, word: String },
+	/// Word at `line` is longer than feedback `Pattern` can describe
+	TooLong { line: usize, found: usize, max: usize },
+	/// Word at `line` contains a letter that is not a single character
+	/// even in composed form, e.g. a letter with two accents
+	MultiCharLetter { line: usize, letter: String },
+	/// Guess pool words have different length than the solution pool words
+	PoolLength { expected: usize, found: usize },
+}
+
+impl fmt::Display for DatabaseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Io(err) => write!(f, "{}", err),
+			Self::Empty => write!(f, "dictionary contains no words"),
+			Self::MixedLength {
+				line,
+				expected,
+				found,
+			} => write!(
+				f,
+				"line {}: word has {} letters, expected {}",
+				line, found, expected
+			),
+			Self::NonAlphabetic { line, word } => {
+				write!(f, "line {}: \"{}\" contains non-alphabetic characters", line, word)
+			}
+			Self::TooLong { line, found, max } => write!(
+				f,
+				"line {}: word has {} letters, at most {} are supported",
+				line, found, max
+			),
+			Self::MultiCharLetter { line, letter } => write!(
+				f,
+				"line {}: letter \"{}\" has no single character form",
+				line, letter
+			),
+			Self::PoolLength { expected, found } => write!(
+				f,
+				"words have {} letters, solutions have {}",
+				found, expected
+			),
+		}
+	}
+}
+
+impl Error for DatabaseError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::Io(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for DatabaseError {
+	fn from(err: io::Error) -> Self {
+		Self::Io(err)
+	}
+}
+
+/// Dictionary that failed to load, see `load_pools`
+#[derive(Debug)]
+pub struct LoadError {
+	pub path: String,
+	pub error: DatabaseError,
+}
+
+impl fmt::Display for LoadError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "\"{}\": {}", self.path, self.error)
+	}
+}
+
+impl Error for LoadError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		Some(&self.error)
+	}
+}
+
+/// Loads solution pool and optional guess pool of the same word length
+///
+/// # Example
+/// ```
+/// use lib::database::{load_pools, DatabaseError};
+///
+/// let (solutions, guesses) = load_pools("../data/test_dict.txt", None).unwrap();
+/// assert!(guesses.is_none());
+/// assert_eq!(solutions.get_word_len(), 5);
+/// let err = load_pools("../data/test_dict.txt", Some("../data/test_dict_11.txt")).unwrap_err();
+/// assert_eq!(err.path, "../data/test_dict_11.txt");
+/// assert!(matches!(err.error, DatabaseError::PoolLength { expected: 5, found: 11 }));
+/// ```
+pub fn load_pools(
+	solutions: &str,
+	guesses: Option<&str>,
+) -> Result<(Database, Option<Database>), LoadError> {
+	let load = |path: &str| {
+		Database::try_load(path).map_err(|error| LoadError {
+			path: path.to_string(),
+			error,
+		})
+	};
+	let solutions_db = load(solutions)?;
+	let guesses_db = match guesses {
+		Some(path) => {
+			let database = load(path)?;
+			check_pools(&solutions_db, &database).map_err(|error| LoadError {
+				path: path.to_string(),
+				error,
+			})?;
+			Some(database)
+		}
+		None => None,
+	};
+	Ok((solutions_db, guesses_db))
+}
+
+/// Checks that words of the `guesses` pool have the same length as the `solutions`
+pub fn check_pools(solutions: &Database, guesses: &Database) -> Result<(), DatabaseError> {
+	if guesses.get_word_len() != solutions.get_word_len() {
+		return Err(DatabaseError::PoolLength {
+			expected: solutions.get_word_len(),
+			found: guesses.get_word_len(),
+		});
+	}
+	Ok(())
+}
+
+/// Brings `word` to the form the database stores words in
+///
+/// Letters are composed (Unicode NFC), so e.g. an accented letter typed
+/// as a base letter followed by a combining accent becomes a single character,
+/// and lowercased.
+///
+/// # Example
+/// ```
+/// use lib::database::normalize_word;
+///
+/// assert_eq!(normalize_word(" Ko\u{63}\u{30c}ka"), "kočka");
+/// assert_eq!(normalize_word("kočka").chars().count(), 5);
+/// ```
+pub fn normalize_word(word: &str) -> String {
+	word.trim().nfc().collect::<String>().to_lowercase()
+}
 
 /// Main database struct
 ///
@@ -17,10 +187,14 @@ use std::path::Path;
 ///
 /// WIP
 /// perhaps we dont need discarded, however lets keep if for now
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Database {
 	available: IndexSet<String>,
 	discarded: IndexSet<String>,
+	/// All words in load order, `reset` restores it so that tie-breaks
+	/// don't depend on previous games
+	words: Arc<IndexSet<String>>,
+	word_len: usize,
 }
 
 impl Database {
@@ -31,7 +205,7 @@ impl Database {
 
 	/// Creates the database from a file
 	///
-	/// Panics on file opening error or line reading error
+	/// Panics on any error `Database::try_load` would return
 	///
 	/// # Example
 	/// ```
@@ -44,36 +218,146 @@ impl Database {
 	where
 		P: AsRef<Path> + Debug,
 	{
-		let file = File::open(filename).expect("File error");
+		match Self::try_load(&filename) {
+			Ok(database) => database,
+			Err(err) => panic!("Failed to load {:?}: {}", filename, err),
+		}
+	}
+
+	/// Creates the database from a file, one word per line
+	///
+	/// Blank lines are skipped and words are normalized, see `normalize_word`.
+	/// All words must be of the same length, at most `Pattern::MAX_LEN` letters,
+	/// and consist of alphabetic characters only. Length is counted in letters,
+	/// so dictionaries of any alphabet work.
+	///
+	/// # Example
+	/// ```
+	/// use lib::database::{Database, DatabaseError};
+	///
+	/// assert!(Database::try_load("../data/test_dict.txt").is_ok());
+	/// let czech = Database::try_load("../data/test_dict_cs.txt").unwrap();
+	/// assert_eq!(czech.get_word_len(), 5);
+	/// assert!(czech.contains("příze"));
+	/// let long = Database::try_load("../data/test_dict_11.txt").unwrap();
+	/// assert_eq!(long.get_word_len(), 11);
+	/// let missing = Database::try_load("../data/missing.txt");
+	/// assert!(matches!(missing, Err(DatabaseError::Io(_))));
+	/// ```
+	pub fn try_load<P>(filename: P) -> Result<Self, DatabaseError>
+	where
+		P: AsRef<Path> + Debug,
+	{
+		let file = File::open(filename)?;
 		let reader = BufReader::new(file);
 		let mut all_words: IndexSet<String> = IndexSet::new();
-		for line in reader.lines() {
-			all_words.insert(line.expect("line error").to_lowercase());
-		}
-		let l = all_words[0].len();
-		for word in all_words.iter() {
-			assert!(
-				word.len() == l,
-				"Words in supplied dictionary are not of same length"
-			);
+		let mut expected: Option<usize> = None;
+		for (i, line) in reader.lines().enumerate() {
+			let line = line?;
+			let word = normalize_word(&line);
+			if word.is_empty() {
+				continue;
+			}
+			if let Some(letter) = word.graphemes(true).find(|g| g.chars().count() > 1) {
+				return Err(DatabaseError::MultiCharLetter {
+					line: i + 1,
+					letter: letter.to_string(),
+				});
+			}
+			if !word.chars().all(char::is_alphabetic) {
+				return Err(DatabaseError::NonAlphabetic { line: i + 1, word });
+			}
+			let len = word.chars().count();
+			match expected {
+				None if len > Pattern::MAX_LEN => {
+					return Err(DatabaseError::TooLong {
+						line: i + 1,
+						found: len,
+						max: Pattern::MAX_LEN,
+					})
+				}
+				None => expected = Some(len),
+				Some(expected) if expected != len => {
+					return Err(DatabaseError::MixedLength {
+						line: i + 1,
+						expected,
+						found: len,
+					})
+				}
+				_ => {}
+			}
+			all_words.insert(word);
 		}
-		Self {
+		let word_len = match expected {
+			Some(len) => len,
+			None => return Err(DatabaseError::Empty),
+		};
+		Ok(Self {
+			words: Arc::new(all_words.clone()),
 			available: all_words,
+			word_len,
 			..Default::default()
-		}
+		})
 	}
 
 	/// Returns random word from the available set
 	/// Does not consume.
-	pub fn get_random(&self) -> String {
-		let mut rng = thread_rng();
-		let i = rng.gen_range(0..self.available.len()) as usize;
+	///
+	/// Pass seeded `rng`, e.g. `StdRng::seed_from_u64`, for reproducible picks
+	/// or `thread_rng()` otherwise.
+	///
+	/// Panics if no words are available
+	///
+	/// # Example
+	/// ```
+	/// use lib::database::Database;
+	/// use rand::rngs::StdRng;
+	/// use rand::SeedableRng;
+	///
+	/// let database = Database::load("../data/test_dict.txt");
+	/// let word = database.get_random(&mut StdRng::seed_from_u64(42));
+	/// assert_eq!(word, database.get_random(&mut StdRng::seed_from_u64(42)));
+	/// ```
+	pub fn get_random<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+		let i = rng.gen_range(0..self.available.len());
 		self.available.get_index(i).unwrap().clone()
 	}
 
+	/// Returns word of the day, the same for given `day` and `seed` on every machine
+	///
+	/// `day` is any consecutive day number, e.g. days since 1 January 1 CE.
+	/// The word depends on the order of available words, so everyone has to
+	/// load the same dictionary. Different `seed` gives different sequence of words.
+	/// `None` if no words are available.
+	///
+	/// # Example
+	/// ```
+	/// use lib::database::Database;
+	///
+	/// let database = Database::load("../data/test_dict.txt");
+	/// let word = database.get_daily(738000, 0).unwrap();
+	/// assert_eq!(Some(word.clone()), database.get_daily(738000, 0));
+	/// assert!(database.contains(&word));
+	/// assert_eq!(Database::new().get_daily(738000, 0), None);
+	/// ```
+	pub fn get_daily(&self, day: i64, seed: u64) -> Option<String> {
+		if self.available.is_empty() {
+			return None;
+		}
+		// splitmix64 finalizer, so that following days get unrelated words
+		let mut x = (day as u64)
+			.wrapping_add(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+			.wrapping_add(0x9e37_79b9_7f4a_7c15);
+		x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+		x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+		x ^= x >> 31;
+		let i = (x % self.available.len() as u64) as usize;
+		self.available.get_index(i).cloned()
+	}
+
 	/// Moves given word from available to discarded
 	/// Panics if word not in the available set
-	pub fn discard(&mut self, word: &String) {
+	pub fn discard(&mut self, word: &str) {
 		let w = self
 			.available
 			.take(word)
@@ -85,85 +369,9 @@ impl Database {
 		self.available.len()
 	}
 
-	/// Removes all words from the available set that DON'T HAVE given `letter` at specified `position`
-	fn prune_letter_at_position(&mut self, letter: char, position: usize) {
-		let mut to_prune: IndexSet<String> = IndexSet::new();
-		for word in self.available.iter() {
-			let c = word.chars().nth(position).expect("postion out of index");
-			if c != letter {
-				to_prune.insert(word.clone());
-			}
-		}
-		for word in to_prune.iter() {
-			self.discard(word);
-		}
-	}
-
-	/// Removes all words from the available set that HAVE given `letter` at specified `position`
-	/// This can be used when guessed correct letter, but at wrong position
-	fn prune_letter_at_incorrect_position(&mut self, letter: char, position: usize) {
-		let mut to_prune: IndexSet<String> = IndexSet::new();
-		for word in self.available.iter() {
-			let c = word.chars().nth(position).expect("postion out of index");
-			if c == letter {
-				to_prune.insert(word.clone());
-			}
-		}
-		for word in to_prune.iter() {
-			self.discard(word);
-		}
-	}
-
-	/// Removes all words that don't contain given `letter`.
-	fn prune_letter_any_position(&mut self, letter: char) {
-		let mut to_prune: IndexSet<String> = IndexSet::new();
-		for word in self.available.iter() {
-			if !word.contains(letter) {
-				to_prune.insert(word.clone());
-			}
-		}
-		for word in to_prune.iter() {
-			self.discard(word);
-		}
-	}
-
-	/// Removes all words that don't have enough instances of given letter
-	fn prune_less_than_n_letter(&mut self, letter: char, n: usize) {
-		let mut to_prune: IndexSet<String> = IndexSet::new();
-		for word in self.available.iter() {
-			if n > word.matches(letter).count() {
-				to_prune.insert(word.clone());
-			}
-		}
-		for word in to_prune.iter() {
-			self.discard(word);
-		}
-	}
-
-	/// Removes all words that contain specific letter
-	fn prune_letter(&mut self, letter: char) {
-		let mut to_prune: IndexSet<String> = IndexSet::new();
-		for word in self.available.iter() {
-			if word.contains(letter) {
-				to_prune.insert(word.clone());
-			}
-		}
-		for word in to_prune.iter() {
-			self.discard(word);
-		}
-	}
-
-	/// Removes all words that contain more than n instances of given letter
-	fn prune_more_than_n_letter(&mut self, letter: char, n: usize) {
-		let mut to_prune: IndexSet<String> = IndexSet::new();
-		for word in self.available.iter() {
-			if word.matches(letter).count() > n {
-				to_prune.insert(word.clone());
-			}
-		}
-		for word in to_prune.iter() {
-			self.discard(word);
-		}
+	/// Length of words in the database, 0 if empty
+	pub fn get_word_len(&self) -> usize {
+		self.word_len
 	}
 
 	/// Available words getter (pointer)
@@ -171,62 +379,50 @@ impl Database {
 		&self.available
 	}
 
-	/// GREEN status - aka correct letter at correct position
-	///
-	/// This results in moving all words that do not have given
-	/// `letter` at the specific `position` from available set to
-	/// discarded set.
-	pub fn prune_green(&mut self, letter: char, position: usize) {
-		self.prune_letter_at_position(letter, position);
-	}
-
-	/// YELLOW status - aka  correct letter at incorrect position
-	///
-	/// This results in moving all words that HAVE given
-	/// `letter` at the specific `position` from available set to
-	/// discarded set. Also all words that do not contain `letter` at all.
-	pub fn prune_yellow(&mut self, letter: char, position: usize) {
-		self.prune_letter_at_incorrect_position(letter, position);
-		self.prune_letter_any_position(letter);
-	}
-
-	pub fn prune_n_yellow(&mut self, letter: char, position: usize, count: usize) {
-		self.prune_letter_at_incorrect_position(letter, position);
-		self.prune_less_than_n_letter(letter, count);
-	}
-
-	/// GREY status - aka incorrect letter
+	/// Moves all available words not matching `constraints` to discarded set
 	///
-	/// This results in moving all words that contain given
-	/// `letter` from available set to
-	/// discarded set.
-	pub fn prune_grey(&mut self, letter: char) {
-		self.prune_letter(letter);
-	}
-
-	pub fn prune_n_grey(&mut self, letter: char, count: usize) {
-		self.prune_more_than_n_letter(letter, count);
+	/// Single pass over the available set.
+	pub fn prune(&mut self, constraints: &Constraints) {
+		let (available, discarded): (IndexSet<String>, IndexSet<String>) = self
+			.available
+			.drain(..)
+			.partition(|word| constraints.matches(word));
+		self.available = available;
+		self.discarded.extend(discarded);
 	}
 
 	/// Checks if given `word` is in the database
 	/// either available or discarded
-	pub fn contains(&self, word: &String) -> bool {
+	pub fn contains(&self, word: &str) -> bool {
 		self.available_contains(word) || self.discarded_contains(word)
 	}
 
 	/// Checks if the `available` set contains given `word`
-	pub fn available_contains(&self, word: &String) -> bool {
+	pub fn available_contains(&self, word: &str) -> bool {
 		self.available.contains(word)
 	}
 
 	/// Checks if the `discarded` set contains given `word`
-	fn discarded_contains(&self, word: &String) -> bool {
+	fn discarded_contains(&self, word: &str) -> bool {
 		self.discarded.contains(word)
 	}
 
-	/// Returns discarded back to available set.
+	/// Returns discarded back to available set, in the order words were loaded
+	///
+	/// # Example
+	/// ```
+	/// use lib::database::Database;
+	///
+	/// let mut database = Database::load("../data/test_dict.txt");
+	/// let loaded = database.get_available().clone();
+	/// let last = loaded.get_index(loaded.len() - 1).unwrap().clone();
+	/// database.discard(loaded.get_index(0).unwrap());
+	/// database.discard(&last);
+	/// database.reset();
+	/// assert!(database.get_available().iter().eq(loaded.iter()));
+	/// ```
 	pub fn reset(&mut self) {
-		self.available.extend(self.discarded.clone());
+		self.available = IndexSet::clone(&self.words);
 		self.discarded.clear();
 	}
 }
@@ -236,6 +432,8 @@ impl Default for Database {
 		Self {
 			available: IndexSet::new(),
 			discarded: IndexSet::new(),
+			words: Arc::default(),
+			word_len: 0,
 		}
 	}
 }
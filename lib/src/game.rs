@@ -1,14 +1,18 @@
 //! Game of Wordle module
 //!
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::error::Error;
 use std::fmt;
-use std::fmt::Display;
+use std::sync::Arc;
 
 /// Enum to show a letter status
 /// Grey - letter not in word
 /// Yellow - letter in word
 /// Greem - letter at correct position
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LetterStatus {
     Grey(char),
     Yellow(char),
@@ -32,13 +36,26 @@ impl LetterStatus {
         }
     }
 
-    pub fn is_green(&self) -> bool {
+    /// Square used in the share text, see `share_text`
+    pub fn as_emoji(&self) -> char {
         match *self {
-            LetterStatus::Green(_) => true,
-            _ => false,
+            Self::Green(_) => '\u{1F7E9}',
+            Self::Grey(_) => '\u{2B1B}',
+            Self::Yellow(_) => '\u{1F7E8}',
         }
     }
 
+    /// The letter itself
+    pub fn get_letter(&self) -> char {
+        match *self {
+            Self::Green(c) | Self::Grey(c) | Self::Yellow(c) => c,
+        }
+    }
+
+    pub fn is_green(&self) -> bool {
+        matches!(*self, LetterStatus::Green(_))
+    }
+
     pub fn from_chars(c: char, status: char) -> Option<Self> {
         match status {
             'G' => Some(Self::Green(c)),
@@ -50,6 +67,8 @@ impl LetterStatus {
 }
 
 /// Struct to handle guessed word status
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WordStatus {
     data: Vec<LetterStatus>,
 }
@@ -69,18 +88,38 @@ impl WordStatus {
         out
     }
 
-    pub fn from_strings(input: &String, status: &String) -> Self {
-        assert!(
-            input.len() == status.len(),
-            "Input and status strings are different lenght"
-        );
+    /// Panics on invalid status string, see `WordStatus::try_from_strings`
+    pub fn from_strings(input: &str, status: &str) -> Self {
+        Self::try_from_strings(input, status).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Pairs letters of `input` with status chars `G`, `Y` or `X` (case insensitive)
+    ///
+    /// # Example
+    /// ```
+    /// use lib::game::{StatusError, WordStatus};
+    ///
+    /// assert!(WordStatus::try_from_strings("crane", "gyxxy").is_ok());
+    /// assert_eq!(
+    ///     WordStatus::try_from_strings("crane", "gyx"),
+    ///     Err(StatusError::WrongLength { expected: 5, found: 3 })
+    /// );
+    /// assert_eq!(
+    ///     WordStatus::try_from_strings("crane", "gyxxb"),
+    ///     Err(StatusError::InvalidChar('B'))
+    /// );
+    /// ```
+    pub fn try_from_strings(input: &str, status: &str) -> Result<Self, StatusError> {
+        let (expected, found) = (input.chars().count(), status.chars().count());
+        if expected != found {
+            return Err(StatusError::WrongLength { expected, found });
+        }
         let s = status.to_uppercase();
         let mut ret = Self::default();
-        for (p, c) in input.chars().enumerate() {
-            let sc = s.chars().nth(p).unwrap();
-            ret.push(LetterStatus::from_chars(c, sc).expect("Unknown char in status string"));
+        for (c, sc) in input.chars().zip(s.chars()) {
+            ret.push(LetterStatus::from_chars(c, sc).ok_or(StatusError::InvalidChar(sc))?);
         }
-        ret
+        Ok(ret)
     }
 
     /// Checks if all letters have `LetterStatus::Green`
@@ -110,6 +149,10 @@ impl WordStatus {
         self.data.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
     pub fn iter(&self) -> std::slice::Iter<'_, LetterStatus> {
         self.data.iter()
     }
@@ -139,12 +182,16 @@ impl WordStatus {
         count
     }
 
+    /// Guessed word the status belongs to
+    pub fn get_word(&self) -> String {
+        self.data.iter().map(LetterStatus::get_letter).collect()
+    }
+
     pub fn get_green_chars(&self) -> String {
         let mut ret = String::new();
         for val in self.data.iter() {
-            match val {
-                LetterStatus::Green(c) => ret.push(*c),
-                _ => {}
+            if let LetterStatus::Green(c) = val {
+                ret.push(*c);
             }
         }
         ret
@@ -153,25 +200,264 @@ impl WordStatus {
     pub fn get_yellow_chars(&self) -> String {
         let mut ret = String::new();
         for val in self.data.iter() {
-            match val {
-                LetterStatus::Yellow(c) => ret.push(*c),
-                _ => {}
+            if let LetterStatus::Yellow(c) = val {
+                ret.push(*c);
             }
         }
         ret
     }
+
+    /// Row of colored squares, hiding the letters
+    pub fn as_emoji(&self) -> String {
+        self.data.iter().map(LetterStatus::as_emoji).collect()
+    }
 }
 
-impl Default for WordStatus {
-    fn default() -> Self {
-        Self { data: Vec::new() }
+/// Renders finished game as the familiar share text
+///
+/// Header holds `title` (e.g. puzzle name and number), number of guesses
+/// or `X` if not solved, out of `max_attempts`, and `*` in hard mode.
+/// Each guess follows as a row of squares.
+///
+/// # Example
+/// ```
+/// use lib::game::{share_text, WordStatus};
+///
+/// let history = vec![
+///     WordStatus::from_strings("crane", "gyxxy"),
+///     WordStatus::from_strings("cover", "ggggg"),
+/// ];
+/// assert_eq!(
+///     share_text("Wordle 42", &history, 6, false),
+///     "Wordle 42 2/6\n\n\u{1F7E9}\u{1F7E8}\u{2B1B}\u{2B1B}\u{1F7E8}\n\u{1F7E9}\u{1F7E9}\u{1F7E9}\u{1F7E9}\u{1F7E9}"
+/// );
+/// ```
+pub fn share_text(
+    title: &str,
+    history: &[WordStatus],
+    max_attempts: usize,
+    hard_mode: bool,
+) -> String {
+    let solved = history.last().is_some_and(WordStatus::is_correct);
+    let score = if solved {
+        history.len().to_string()
+    } else {
+        "X".to_string()
+    };
+    let mut out = format!(
+        "{} {}/{}{}\n",
+        title,
+        score,
+        max_attempts,
+        if hard_mode { "*" } else { "" }
+    );
+    for status in history.iter() {
+        out.push('\n');
+        out += &status.as_emoji();
+    }
+    out
+}
+
+/// Reason why a guess is not allowed in hard mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HardModeViolation {
+    /// Revealed green `letter` must be reused at `position` (0-based)
+    MissingGreen { letter: char, position: usize },
+    /// Revealed `letter` must be used at least `count` times
+    MissingLetter { letter: char, count: usize },
+}
+
+/// English ordinal for 1-based `n`, e.g. "1st", "4th", "11th"
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+impl fmt::Display for HardModeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingGreen { letter, position } => write!(
+                f,
+                "{} letter must be {}",
+                ordinal(position + 1),
+                letter.to_uppercase()
+            ),
+            Self::MissingLetter { letter, count: 1 } => {
+                write!(f, "Guess must contain {}", letter.to_uppercase())
+            }
+            Self::MissingLetter { letter, count } => {
+                write!(f, "Guess must contain {} {}s", count, letter.to_uppercase())
+            }
+        }
+    }
+}
+
+impl Error for HardModeViolation {}
+
+/// Constraints revealed so far that hard mode guesses must respect
+///
+/// Greens must be reused at their position and every revealed
+/// letter (green or yellow) must be present in subsequent guesses.
+///
+/// # Example
+/// ```
+/// use lib::game::{HardModeRules, WordStatus};
+///
+/// let mut rules = HardModeRules::new();
+/// rules.update(&WordStatus::from_strings("crane", "xgxxy"));
+/// assert!(rules.check("freed").is_ok());
+/// assert_eq!(rules.check("roses").unwrap_err().to_string(), "2nd letter must be R");
+/// assert_eq!(rules.check("proud").unwrap_err().to_string(), "Guess must contain E");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HardModeRules {
+    greens: Vec<Option<char>>,
+    required: Vec<(char, usize)>,
+}
+
+impl HardModeRules {
+    /// Constructor, no constraints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accumulates constraints revealed by `status`
+    pub fn update(&mut self, status: &WordStatus) {
+        if self.greens.len() < status.len() {
+            self.greens.resize(status.len(), None);
+        }
+        for (position, letter_status) in status.iter().enumerate() {
+            let letter = match letter_status {
+                LetterStatus::Green(c) => {
+                    self.greens[position] = Some(*c);
+                    *c
+                }
+                LetterStatus::Yellow(c) => *c,
+                LetterStatus::Grey(_) => continue,
+            };
+            let (green, yellow, _) = status.char_count(letter);
+            match self.required.iter_mut().find(|(c, _)| *c == letter) {
+                Some((_, count)) => *count = (*count).max(green + yellow),
+                None => self.required.push((letter, green + yellow)),
+            }
+        }
+    }
+
+    /// Checks if `word` respects all revealed constraints
+    ///
+    /// Greens are checked first, by position.
+    pub fn check(&self, word: &str) -> Result<(), HardModeViolation> {
+        for (position, green) in self.greens.iter().enumerate() {
+            if let Some(letter) = green {
+                if word.chars().nth(position) != Some(*letter) {
+                    return Err(HardModeViolation::MissingGreen {
+                        letter: *letter,
+                        position,
+                    });
+                }
+            }
+        }
+        for (letter, count) in self.required.iter() {
+            if word.matches(*letter).count() < *count {
+                return Err(HardModeViolation::MissingLetter {
+                    letter: *letter,
+                    count: *count,
+                });
+            }
+        }
+        Ok(())
     }
 }
 
+/// Reason why a status string can't be paired with a word
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusError {
+    /// Status has `found` chars, word has `expected` letters
+    WrongLength { expected: usize, found: usize },
+    /// Char other than `G`, `Y` or `X`
+    InvalidChar(char),
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::WrongLength { expected, found } => {
+                write!(f, "Status has {} chars, expected {}", found, expected)
+            }
+            Self::InvalidChar(c) => write!(f, "Unknown status char '{}', use G, Y or X", c),
+        }
+    }
+}
+
+impl Error for StatusError {}
+
+/// Reason why a guess was rejected by `Wordle::guess_word`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError {
+    /// Guess has `found` letters, hidden word has `expected`
+    WrongLength { expected: usize, found: usize },
+    /// Word was already guessed or attempts ran out
+    GameFinished,
+    /// Guess was refused by the attached validator
+    NotInDictionary(String),
+    /// Guess doesn't use revealed hints in hard mode
+    HardMode(HardModeViolation),
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::WrongLength { expected, found } => write!(
+                f,
+                "Guess has {} letters, expected {}",
+                found, expected
+            ),
+            Self::GameFinished => write!(f, "Game is already finished"),
+            Self::NotInDictionary(word) => write!(f, "\"{}\" is not in the dictionary", word),
+            Self::HardMode(violation) => write!(f, "{}", violation),
+        }
+    }
+}
+
+impl Error for GuessError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::HardMode(violation) => Some(violation),
+            _ => None,
+        }
+    }
+}
+
+impl From<HardModeViolation> for GuessError {
+    fn from(violation: HardModeViolation) -> Self {
+        Self::HardMode(violation)
+    }
+}
+
+/// Checks if a guessed word is acceptable, e.g. present in a dictionary
+pub type Validator = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Single game of Wordle
+///
+/// With the `serde` feature the game can be saved and restored,
+/// the validator is not saved and has to be set again.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Wordle {
     word: String,
     max_attempts: usize,
     attempts: usize,
+    solved: bool,
+    hard_mode: Option<HardModeRules>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    validator: Option<Validator>,
 }
 
 impl Default for Wordle {
@@ -180,6 +466,9 @@ impl Default for Wordle {
             word: "hello".to_string(),
             max_attempts: 6,
             attempts: 0,
+            solved: false,
+            hard_mode: None,
+            validator: None,
         }
     }
 }
@@ -203,6 +492,36 @@ impl Wordle {
         self.max_attempts
     }
 
+    /// Turns hard mode on or off
+    ///
+    /// In hard mode revealed hints must be used in subsequent guesses.
+    /// Constraints are accumulated only from guesses made while it is on.
+    pub fn set_hard_mode(&mut self, enabled: bool) {
+        self.hard_mode = if enabled {
+            Some(self.hard_mode.take().unwrap_or_default())
+        } else {
+            None
+        };
+    }
+
+    /// Checks if hard mode is on
+    pub fn is_hard_mode(&self) -> bool {
+        self.hard_mode.is_some()
+    }
+
+    /// Checks if `word` is allowed by hard mode rules, always `Ok` in normal mode
+    pub fn check_hard_mode(&self, word: &str) -> Result<(), HardModeViolation> {
+        match &self.hard_mode {
+            Some(rules) => rules.check(word),
+            None => Ok(()),
+        }
+    }
+
+    /// Attaches validator, guesses it refuses are rejected with `GuessError::NotInDictionary`
+    pub fn set_validator(&mut self, validator: Validator) {
+        self.validator = Some(validator);
+    }
+
     /// Check if game is over
     ///
     /// Returns bool if `attempts` >= `max_attempts`
@@ -210,63 +529,103 @@ impl Wordle {
         self.attempts >= self.max_attempts
     }
 
+    /// Checks if the hidden word was guessed
+    pub fn is_solved(&self) -> bool {
+        self.solved
+    }
+
+    /// Checks if no more guesses are accepted, either solved or out of attempts
+    pub fn is_finished(&self) -> bool {
+        self.solved || self.game_over()
+    }
+
+    /// Number of accepted guesses so far
+    pub fn get_attempts(&self) -> usize {
+        self.attempts
+    }
+
     /// Checks guessed word against the hidden one. Outputs "color code" per letter
     /// as described in rules.
-    pub fn guess_word(&mut self, word: &String) -> WordStatus {
-        // Sanity check, guessed word must be same length
-        assert!(
-            self.word.len() == word.len(),
-            "Guessed word is incorrect length"
-        );
-        // Return vector
-        let mut status = WordStatus::new();
-        for (position, letter) in word.chars().enumerate() {
-            let c = self.word.chars().nth(position).unwrap();
-            // eprintln!("({}: {} | {})", position, letter, c);
-            if letter == c {
-                status.push(LetterStatus::Green(letter));
-            } else if self.word.contains(letter) {
-                status.push(LetterStatus::Yellow(letter));
-            } else {
-                status.push(LetterStatus::Grey(letter));
-            }
+    ///
+    /// Rejected guesses don't count as attempts.
+    ///
+    /// # Example
+    /// ```
+    /// use lib::game::{GuessError, Wordle};
+    ///
+    /// let mut wordle = Wordle::new("crane".to_string());
+    /// assert!(matches!(wordle.guess_word("car"), Err(GuessError::WrongLength { .. })));
+    /// assert_eq!(wordle.guess_word("crane").unwrap().as_string(), "GGGGG");
+    /// assert_eq!(wordle.guess_word("crane"), Err(GuessError::GameFinished));
+    /// ```
+    pub fn guess_word(&mut self, word: &str) -> Result<WordStatus, GuessError> {
+        if self.is_finished() {
+            return Err(GuessError::GameFinished);
         }
-
-        // Check for letters multiple instances
-        let mut result = WordStatus::new();
-        for letter_status in status.iter() {
-            match letter_status {
-                // We care only about yellows - greens are set and grey are as low as they can be
-                LetterStatus::Yellow(letter) => {
-                    let cnt_org = self.word.matches(*letter).count();
-                    let cnt_guess = word.matches(*letter).count();
-                    // if there's more instances than in original word
-                    if cnt_guess > cnt_org {
-                        // we check what kind of statuses they have
-                        // (green, yellow, grey)
-                        let cnt_status = status.char_count(*letter);
-                        let cnt_res = result.char_count(*letter);
-                        if cnt_status.0 + cnt_res.1 >= cnt_org {
-                            result.push(LetterStatus::Grey(*letter))
-                        } else {
-                            result.push(*letter_status)
-                        }
-                    } else {
-                        result.push(*letter_status);
-                    }
-                }
-                _ => {
-                    result.push(*letter_status);
-                }
+        // Guessed word must be same length, in letters
+        let (expected, found) = (self.word.chars().count(), word.chars().count());
+        if expected != found {
+            return Err(GuessError::WrongLength { expected, found });
+        }
+        if let Some(validator) = &self.validator {
+            if !validator(word) {
+                return Err(GuessError::NotInDictionary(word.to_string()));
             }
         }
-        // Sanity check, There should be same number of elements in `status`
-        // as is in the word
-        assert!(
-            result.len() == word.len(),
-            "WordStatus as different length than the original word"
-        );
+        // In hard mode the guess must respect revealed hints
+        self.check_hard_mode(word)?;
+        let result = evaluate(&self.word, word);
+        if let Some(rules) = self.hard_mode.as_mut() {
+            rules.update(&result);
+        }
         self.attempts += 1;
-        result
+        self.solved = result.is_correct();
+        Ok(result)
+    }
+}
+
+/// Computes the status of `guess` against hidden `word` without touching any game state.
+///
+/// This is the feedback `Wordle::guess_word` responds with, usable on its own
+/// when simulating guesses (e.g. for suggestion scoring).
+///
+/// Letters in place are marked green first. Then, left to right, a letter
+/// is yellow only while the hidden word has an unmatched instance of it left,
+/// so repeated letters get no more greens and yellows than the word contains.
+///
+/// # Example
+/// ```
+/// use lib::game::evaluate;
+///
+/// assert_eq!(evaluate("crane", "cover").as_string(), "GXXYY");
+/// // Only one "e" in the word, so only the first "e" of the guess is yellow
+/// assert_eq!(evaluate("abide", "speed").as_string(), "XXYXY");
+/// // Green takes precedence over an earlier yellow
+/// assert_eq!(evaluate("abide", "eerie").as_string(), "XXXYG");
+/// ```
+pub fn evaluate(word: &str, guess: &str) -> WordStatus {
+    let word: Vec<char> = word.chars().collect();
+    let guess: Vec<char> = guess.chars().collect();
+    assert!(
+        word.len() == guess.len(),
+        "Guess and word are different length"
+    );
+    // Letters of the word not matched by a green or yellow yet
+    let mut unmatched: Vec<Option<char>> = word
+        .iter()
+        .zip(guess.iter())
+        .map(|(w, g)| if w == g { None } else { Some(*w) })
+        .collect();
+    let mut result = WordStatus::new();
+    for (position, letter) in guess.iter().enumerate() {
+        if word[position] == *letter {
+            result.push(LetterStatus::Green(*letter));
+        } else if let Some(found) = unmatched.iter_mut().find(|c| **c == Some(*letter)) {
+            *found = None;
+            result.push(LetterStatus::Yellow(*letter));
+        } else {
+            result.push(LetterStatus::Grey(*letter));
+        }
     }
+    result
 }
@@ -1,45 +1,71 @@
+use indexmap::IndexSet;
 use std::collections::HashMap;
 
+/// Letter frequencies in percents, computed from a word list
+///
+/// `map` holds share of each letter among all letters of the words,
+/// `positions` share of each letter among the letters at given position.
+///
+/// # Example
+/// ```
+/// use indexmap::IndexSet;
+/// use lib::letter_frequency::LetterFrequencyMap;
+///
+/// let words: IndexSet<String> = ["crane", "cover"].iter().map(|w| w.to_string()).collect();
+/// let frequencies = LetterFrequencyMap::from_words(&words);
+/// assert_eq!(frequencies.get_frequency('c'), Some(&20.0));
+/// assert_eq!(frequencies.get_position_frequency(0, 'c'), Some(&100.0));
+/// assert_eq!(frequencies.get_position_frequency(1, 'o'), Some(&50.0));
+/// assert_eq!(frequencies.get_frequency('z'), None);
+/// ```
+#[derive(Debug, Default, Clone)]
 pub struct LetterFrequencyMap {
     pub map: HashMap<char, f32>,
+    pub positions: Vec<HashMap<char, f32>>,
 }
 
-/// Letter frequencies are from https://www3.nd.edu/~busiforc/handouts/cryptography/letterfrequencies.html
 impl LetterFrequencyMap {
-    /// Constructor
+    /// Constructor, no frequencies
     pub fn new() -> Self {
-        let map = HashMap::from([
-            ('a', 43.31),
-            ('b', 10.56),
-            ('c', 23.13),
-            ('d', 17.25),
-            ('e', 56.88),
-            ('f', 9.24),
-            ('g', 12.59),
-            ('h', 15.31),
-            ('i', 38.45),
-            ('j', 1.00),
-            ('k', 5.61),
-            ('l', 27.98),
-            ('m', 15.36),
-            ('n', 33.92),
-            ('o', 36.51),
-            ('p', 16.14),
-            ('q', 1.0),
-            ('r', 38.64),
-            ('s', 29.23),
-            ('t', 35.43),
-            ('u', 18.51),
-            ('v', 5.13),
-            ('w', 6.57),
-            ('x', 1.48),
-            ('y', 9.06),
-            ('z', 1.39),
-        ]);
-        LetterFrequencyMap { map }
+        Self::default()
+    }
+
+    /// Computes overall and per position frequencies of letters in `words`
+    pub fn from_words(words: &IndexSet<String>) -> Self {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        let mut position_counts: Vec<HashMap<char, usize>> = Vec::new();
+        let mut total = 0;
+        for word in words.iter() {
+            for (position, c) in word.chars().enumerate() {
+                *counts.entry(c).or_insert(0) += 1;
+                if position_counts.len() <= position {
+                    position_counts.resize(position + 1, HashMap::new());
+                }
+                *position_counts[position].entry(c).or_insert(0) += 1;
+                total += 1;
+            }
+        }
+        let map = percents(&counts, total);
+        let positions = position_counts
+            .iter()
+            .map(|counts| percents(counts, counts.values().sum()))
+            .collect();
+        Self { map, positions }
     }
 
     pub fn get_frequency(&self, c: char) -> Option<&f32> {
         self.map.get(&c)
     }
+
+    /// Frequency of `c` at 0-based `position`
+    pub fn get_position_frequency(&self, position: usize, c: char) -> Option<&f32> {
+        self.positions.get(position)?.get(&c)
+    }
+}
+
+fn percents(counts: &HashMap<char, usize>, total: usize) -> HashMap<char, f32> {
+    counts
+        .iter()
+        .map(|(c, count)| (*c, 100.0 * *count as f32 / total as f32))
+        .collect()
 }
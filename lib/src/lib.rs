@@ -1,5 +1,8 @@
 pub mod assistant;
+pub mod constraints;
 pub mod database;
 pub mod game;
 pub mod suggestion;
+pub mod tree;
 pub mod letter_frequency;
+pub mod pattern;
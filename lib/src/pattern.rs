@@ -0,0 +1,264 @@
+//! Compact feedback pattern module
+//!
+//! `WordStatus` is convenient to work with, but scoring guesses needs the
+//! feedback of every guess against every candidate. `Pattern` packs the feedback
+//! into a single integer and `PatternMatrix` precomputes it for whole word lists.
+
+use crate::game::{LetterStatus, WordStatus};
+use indexmap::IndexSet;
+
+/// Feedback of a guess as a base-3 integer
+///
+/// Letter at position `i` contributes `0` (grey), `1` (yellow) or `2` (green)
+/// times `3^i`. Patterns of words up to `Pattern::MAX_LEN` letters fit.
+///
+/// # Example
+/// ```
+/// use lib::game::WordStatus;
+/// use lib::pattern::Pattern;
+///
+/// let pattern = Pattern::new("cover", "crane");
+/// assert_eq!(pattern, Pattern::from(&WordStatus::from_strings("crane", "gyxxy")));
+/// assert_eq!(pattern.to_status("crane").as_string(), "GYXXY");
+/// assert!(Pattern::new("crane", "crane").is_correct(5));
+/// let long = Pattern::new("temperature", "grandmother");
+/// assert_eq!(long.to_status("grandmother").as_string(), "XYYXXYXGXYY");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pattern(u32);
+
+impl Pattern {
+	/// Longest word a pattern can describe
+	pub const MAX_LEN: usize = 20;
+
+	/// Feedback the game responds with to `guess` when `word` is the hidden one
+	///
+	/// Same as `game::evaluate` without allocating. Panics if the words are
+	/// of different length or longer than `Pattern::MAX_LEN`.
+	pub fn new(word: &str, guess: &str) -> Self {
+		let mut word_chars = ['\0'; Self::MAX_LEN];
+		let mut guess_chars = ['\0'; Self::MAX_LEN];
+		let len = fill(&mut word_chars, word);
+		assert!(
+			fill(&mut guess_chars, guess) == len,
+			"Guess and word are different length"
+		);
+		let mut digits = [0u32; Self::MAX_LEN];
+		// Letters of the hidden word not yet matched by a green or yellow
+		let mut unmatched = [true; Self::MAX_LEN];
+		for i in 0..len {
+			if guess_chars[i] == word_chars[i] {
+				digits[i] = 2;
+				unmatched[i] = false;
+			}
+		}
+		for i in 0..len {
+			if digits[i] != 0 {
+				continue;
+			}
+			if let Some(j) = (0..len).find(|&j| unmatched[j] && word_chars[j] == guess_chars[i]) {
+				digits[i] = 1;
+				unmatched[j] = false;
+			}
+		}
+		Self::from_digits(&digits[..len])
+	}
+
+	/// Pattern of a solved word of `word_len` letters, all greens
+	pub fn correct(word_len: usize) -> Self {
+		assert!(word_len <= Self::MAX_LEN, "Word too long for a pattern");
+		Self((Self::count(word_len) - 1) as u32)
+	}
+
+	/// Checks if all letters of a `word_len` letter word are green
+	pub fn is_correct(self, word_len: usize) -> bool {
+		self == Self::correct(word_len)
+	}
+
+	/// Number of distinct patterns of `word_len` letter words
+	pub fn count(word_len: usize) -> usize {
+		3usize.pow(word_len as u32)
+	}
+
+	/// The integer, unique for each pattern of given word length
+	pub fn get_index(self) -> usize {
+		self.0 as usize
+	}
+
+	/// Expands the pattern to `WordStatus` of given `guess`
+	pub fn to_status(self, guess: &str) -> WordStatus {
+		let mut status = WordStatus::new();
+		let mut value = self.0;
+		for c in guess.chars() {
+			let letter_status = match value % 3 {
+				2 => LetterStatus::Green(c),
+				1 => LetterStatus::Yellow(c),
+				_ => LetterStatus::Grey(c),
+			};
+			status.push(letter_status);
+			value /= 3;
+		}
+		status
+	}
+
+	fn from_digits(digits: &[u32]) -> Self {
+		assert!(digits.len() <= Self::MAX_LEN, "Word too long for a pattern");
+		Self(digits.iter().rev().fold(0, |value, digit| value * 3 + digit))
+	}
+}
+
+/// Copies chars of `word` to `buffer`, returns their count
+fn fill(buffer: &mut [char; Pattern::MAX_LEN], word: &str) -> usize {
+	let mut len = 0;
+	for c in word.chars() {
+		assert!(len < Pattern::MAX_LEN, "Word too long for a pattern");
+		buffer[len] = c;
+		len += 1;
+	}
+	len
+}
+
+/// Panics if `status` is longer than `Pattern::MAX_LEN`
+impl From<&WordStatus> for Pattern {
+	fn from(status: &WordStatus) -> Self {
+		let digits: Vec<u32> = status
+			.iter()
+			.map(|letter_status| match letter_status {
+				LetterStatus::Green(_) => 2,
+				LetterStatus::Yellow(_) => 1,
+				LetterStatus::Grey(_) => 0,
+			})
+			.collect();
+		Self::from_digits(&digits)
+	}
+}
+
+/// Feedback of every guess against every solution
+///
+/// Rows are guesses, columns solutions, both in order of the word lists given.
+///
+/// # Example
+/// ```
+/// use indexmap::IndexSet;
+/// use lib::pattern::{Pattern, PatternMatrix};
+///
+/// let guesses: IndexSet<String> = ["crane", "cover"].iter().map(|w| w.to_string()).collect();
+/// let solutions: IndexSet<String> = ["cover"].iter().map(|w| w.to_string()).collect();
+/// let matrix = PatternMatrix::new(&guesses, &solutions);
+/// assert_eq!(matrix.get(0, 0), Pattern::new("cover", "crane"));
+/// assert_eq!(matrix.lookup("cover", "cover"), Some(Pattern::correct(5)));
+/// assert_eq!(matrix.lookup("raise", "cover"), None);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct PatternMatrix {
+	guesses: IndexSet<String>,
+	solutions: IndexSet<String>,
+	patterns: Vec<Pattern>,
+}
+
+impl PatternMatrix {
+	/// Computes feedback of each of `guesses` against each of `solutions`
+	pub fn new(guesses: &IndexSet<String>, solutions: &IndexSet<String>) -> Self {
+		let mut patterns = Vec::with_capacity(guesses.len() * solutions.len());
+		for guess in guesses.iter() {
+			patterns.extend(solutions.iter().map(|word| Pattern::new(word, guess)));
+		}
+		Self {
+			guesses: guesses.clone(),
+			solutions: solutions.clone(),
+			patterns,
+		}
+	}
+
+	pub fn get_guesses(&self) -> &IndexSet<String> {
+		&self.guesses
+	}
+
+	pub fn get_solutions(&self) -> &IndexSet<String> {
+		&self.solutions
+	}
+
+	/// Index of `word` among the guesses
+	pub fn guess_index(&self, word: &str) -> Option<usize> {
+		self.guesses.get_index_of(word)
+	}
+
+	/// Index of `word` among the solutions
+	pub fn solution_index(&self, word: &str) -> Option<usize> {
+		self.solutions.get_index_of(word)
+	}
+
+	/// Feedback of guess at index `guess` against solution at index `solution`
+	///
+	/// Panics if either index is out of bounds
+	pub fn get(&self, guess: usize, solution: usize) -> Pattern {
+		self.get_row(guess)[solution]
+	}
+
+	/// Feedback of guess at index `guess` against all solutions
+	pub fn get_row(&self, guess: usize) -> &[Pattern] {
+		let width = self.solutions.len();
+		&self.patterns[guess * width..(guess + 1) * width]
+	}
+
+	/// Feedback of `guess` against `solution`, `None` if either word is not in the matrix
+	pub fn lookup(&self, guess: &str, solution: &str) -> Option<Pattern> {
+		Some(self.get(self.guess_index(guess)?, self.solution_index(solution)?))
+	}
+}
+
+/// Source of feedback of guesses against the remaining candidates
+pub enum Feedback<'a> {
+	/// Feedback is computed for every candidate
+	Words(&'a IndexSet<String>),
+	/// Feedback is looked up in the matrix, candidates are given by their solution index
+	Matrix(&'a PatternMatrix, Vec<usize>),
+}
+
+impl<'a> Feedback<'a> {
+	/// Uses `matrix` if given and it contains all the `candidates`
+	pub fn new(candidates: &'a IndexSet<String>, matrix: Option<&'a PatternMatrix>) -> Self {
+		let indices = matrix.and_then(|matrix| {
+			candidates
+				.iter()
+				.map(|word| matrix.solution_index(word))
+				.collect::<Option<Vec<usize>>>()
+		});
+		match (matrix, indices) {
+			(Some(matrix), Some(indices)) => Self::Matrix(matrix, indices),
+			_ => Self::Words(candidates),
+		}
+	}
+
+	/// Number of candidates
+	pub fn len(&self) -> usize {
+		match self {
+			Self::Words(candidates) => candidates.len(),
+			Self::Matrix(_, indices) => indices.len(),
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Feedback of `guess` against each of the candidates
+	pub fn get_patterns(&self, guess: &str) -> Vec<Pattern> {
+		match self {
+			Self::Words(candidates) => candidates
+				.iter()
+				.map(|word| Pattern::new(word, guess))
+				.collect(),
+			Self::Matrix(matrix, indices) => match matrix.guess_index(guess) {
+				Some(row) => {
+					let row = matrix.get_row(row);
+					indices.iter().map(|&i| row[i]).collect()
+				}
+				None => indices
+					.iter()
+					.map(|&i| Pattern::new(&matrix.solutions[i], guess))
+					.collect(),
+			},
+		}
+	}
+}
@@ -1,12 +1,59 @@
 use crate::letter_frequency::LetterFrequencyMap;
+use crate::pattern::{Feedback, Pattern};
 use indexmap::IndexSet;
 use itertools::Itertools;
-use lazy_static::lazy_static;
 use rand::seq::SliceRandom;
+use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering::Equal;
+use std::cmp::Reverse;
+use std::str::FromStr;
 
 const COL_WIDTH: usize = 14;
+const FREQ_COL_WIDTH: usize = 22;
 
+/// Ranking used to order the suggestions
+///
+/// `Unique` - most unique letters first
+/// `Frequency` - highest average letter frequency first
+/// `Entropy` - highest expected information (in bits) over the remaining candidates first
+/// `Minimax` - smallest worst case, i.e. largest group of candidates left after the guess, first
+/// `Positional` - highest average frequency of letters at their positions, i.e. likely greens, first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortMode {
+	Unique,
+	#[default]
+	Frequency,
+	Entropy,
+	Minimax,
+	Positional,
+}
+
+impl SortMode {
+	/// Names accepted by `SortMode::from_str`, used by the binaries' help
+	pub const VARIANTS: [&'static str; 5] =
+		["unique", "frequency", "entropy", "minimax", "positional"];
+}
+
+impl FromStr for SortMode {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"unique" => Ok(Self::Unique),
+			"frequency" => Ok(Self::Frequency),
+			"entropy" => Ok(Self::Entropy),
+			"minimax" => Ok(Self::Minimax),
+			"positional" => Ok(Self::Positional),
+			_ => Err(format!("Unknown sort mode \"{}\"", s)),
+		}
+	}
+}
+
+#[derive(Clone, Default)]
 pub struct SuggestionCollection {
 	pub items: Vec<Suggestion>,
 }
@@ -19,8 +66,7 @@ impl SuggestionCollection {
 
 	/// Sort based on n of unique chars
 	pub fn sort_suggestions(&mut self) {
-		self.items
-			.sort_by(|a, b| b.unique_chars.cmp(&a.unique_chars));
+		self.items.sort_by_key(|a| Reverse(a.unique_chars));
 	}
 
 	/// Sort based on n of frequency score
@@ -32,10 +78,99 @@ impl SuggestionCollection {
 		});
 	}
 
-	pub fn update_with_info(&mut self, info: &String) {
+	/// Sort based on expected information, ties broken by frequency score
+	///
+	/// Expects `update_entropy` to be called beforehand, words without
+	/// computed entropy end up last.
+	pub fn sort_suggestions_entropy(&mut self) {
+		self.items.sort_by(|a, b| {
+			b.entropy
+				.unwrap_or(-1.0)
+				.partial_cmp(&a.entropy.unwrap_or(-1.0))
+				.unwrap_or(Equal)
+				.then(
+					b.avg_frequency
+						.partial_cmp(&a.avg_frequency)
+						.unwrap_or(Equal),
+				)
+		});
+	}
+
+	/// Sort based on worst case bucket size, ties broken by frequency score
+	///
+	/// Expects `update_worst_case` to be called beforehand, words without
+	/// computed worst case end up last.
+	pub fn sort_suggestions_minimax(&mut self) {
+		self.items.sort_by(|a, b| {
+			a.worst_case
+				.unwrap_or(usize::MAX)
+				.cmp(&b.worst_case.unwrap_or(usize::MAX))
+				.then(
+					b.avg_frequency
+						.partial_cmp(&a.avg_frequency)
+						.unwrap_or(Equal),
+				)
+		});
+	}
+
+	/// Sort based on positional frequency score, ties broken by frequency score
+	pub fn sort_suggestions_positional(&mut self) {
+		self.items.sort_by(|a, b| {
+			b.positional_frequency
+				.partial_cmp(&a.positional_frequency)
+				.unwrap_or(Equal)
+				.then(
+					b.avg_frequency
+						.partial_cmp(&a.avg_frequency)
+						.unwrap_or(Equal),
+				)
+		});
+	}
+
+	/// Sorts by given `mode`
+	pub fn sort_by_mode(&mut self, mode: SortMode) {
+		match mode {
+			SortMode::Unique => self.sort_suggestions(),
+			SortMode::Frequency => self.sort_suggestions_freq(),
+			SortMode::Entropy => self.sort_suggestions_entropy(),
+			SortMode::Minimax => self.sort_suggestions_minimax(),
+			SortMode::Positional => self.sort_suggestions_positional(),
+		}
+	}
+
+	/// Computes expected information of every suggestion against the candidates of `feedback`
+	///
+	/// Runs on all cores with the `parallel` feature.
+	pub fn update_entropy(&mut self, feedback: &Feedback) {
+		#[cfg(feature = "parallel")]
+		self.items
+			.par_iter_mut()
+			.for_each(|word| word.update_entropy(feedback));
+		#[cfg(not(feature = "parallel"))]
+		for word in self.items.iter_mut() {
+			word.update_entropy(feedback);
+		}
+	}
+
+	/// Computes largest feedback bucket of every suggestion against the candidates of `feedback`
+	///
+	/// Runs on all cores with the `parallel` feature.
+	pub fn update_worst_case(&mut self, feedback: &Feedback) {
+		#[cfg(feature = "parallel")]
+		self.items
+			.par_iter_mut()
+			.for_each(|word| word.update_worst_case(feedback));
+		#[cfg(not(feature = "parallel"))]
+		for word in self.items.iter_mut() {
+			word.update_worst_case(feedback);
+		}
+	}
+
+	/// Leaves known letters `info` out of unique letters and frequency scores
+	pub fn update_with_info(&mut self, info: &str, frequencies: &LetterFrequencyMap) {
 		for word in self.items.iter_mut() {
 			word.update_unique_with_info(info);
-			word.update_freq_with_info(info);
+			word.update_freq_with_info(info, frequencies);
 		}
 	}
 
@@ -44,7 +179,8 @@ impl SuggestionCollection {
 		self.items[0].get_unique_chars()
 	}
 
-	pub fn get_random_most_unique(&mut self) -> Option<String> {
+	/// Picks one of the words with most unique chars using `rng`
+	pub fn get_random_most_unique<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<String> {
 		if self.items.is_empty() {
 			return None;
 		}
@@ -55,7 +191,7 @@ impl SuggestionCollection {
 				max_arr.push(sugg.get_word_clone())
 			}
 		}
-		max_arr.choose(&mut rand::thread_rng()).cloned()
+		max_arr.choose(rng).cloned()
 	}
 
 	///gets the most frequent with most unique
@@ -75,109 +211,198 @@ impl SuggestionCollection {
 				.partial_cmp(&a.avg_frequency)
 				.unwrap_or(Equal)
 		});
-		Some(max_arr.get(0).unwrap().get_word_clone())
+		Some(max_arr.first().unwrap().get_word_clone())
 	}
 }
 
-impl From<&IndexSet<String>> for SuggestionCollection {
-	fn from(available: &IndexSet<String>) -> Self {
+impl SuggestionCollection {
+	/// Suggestions of `words` scored by given letter `frequencies`
+	pub fn from_words(words: &IndexSet<String>, frequencies: &LetterFrequencyMap) -> Self {
 		let mut suggestion_collection = SuggestionCollection::new();
-		for word in available.iter() {
-			let suggestion = Suggestion::new(word);
+		for word in words.iter() {
+			let suggestion = Suggestion::new(word, frequencies);
 			suggestion_collection.items.push(suggestion);
 		}
 		suggestion_collection
 	}
 }
 
-impl Default for SuggestionCollection {
-	fn default() -> Self {
-		Self { items: Vec::new() }
+/// Suggestions scored by letter frequencies of the `available` words themselves
+impl From<&IndexSet<String>> for SuggestionCollection {
+	fn from(available: &IndexSet<String>) -> Self {
+		Self::from_words(available, &LetterFrequencyMap::from_words(available))
 	}
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Suggestion {
 	word: String,
 	avg_frequency: f32,
+	positional_frequency: f32,
 	unique_chars: i8,
+	entropy: Option<f32>,
+	worst_case: Option<usize>,
 }
 
-/// Lazy static LetterFrequencyMap
-lazy_static! {
-	static ref FREQ_MAP: LetterFrequencyMap = LetterFrequencyMap::new();
-}
-
-fn unique_char_count(word: &String) -> i8 {
+fn unique_char_count(word: &str) -> i8 {
 	let chars: Vec<char> = word.chars().collect::<Vec<_>>();
 	chars.into_iter().unique().count() as i8
 }
 
-fn count_avg_freq(word: &String) -> f32 {
+/// Sum of letter frequencies in `word` divided by `word_len`
+///
+/// `word_len` is the length of the played word, which is more than length
+/// of `word` when known letters were removed from it.
+fn count_avg_freq(word: &str, word_len: usize, frequencies: &LetterFrequencyMap) -> f32 {
 	let mut sum: f32 = 0.0;
 	for c in word.chars() {
-		let val: &f32 = FREQ_MAP.get_frequency(c).unwrap_or(&0.0);
-		sum += f32::from(*val);
+		let val: &f32 = frequencies.get_frequency(c).unwrap_or(&0.0);
+		sum += *val;
 	}
-	sum / 5.0
+	sum / word_len as f32
+}
+
+/// Average frequency of letters of `word` at their positions,
+/// i.e. how likely each letter is to be green
+fn count_positional_freq(word: &str, frequencies: &LetterFrequencyMap) -> f32 {
+	let mut sum: f32 = 0.0;
+	let mut len = 0;
+	for (position, c) in word.chars().enumerate() {
+		sum += frequencies
+			.get_position_frequency(position, c)
+			.unwrap_or(&0.0);
+		len += 1;
+	}
+	sum / len.max(1) as f32
+}
+
+/// Sizes of groups the candidates are partitioned into by their feedback `patterns`
+fn bucket_sizes(mut patterns: Vec<Pattern>) -> Vec<usize> {
+	patterns.sort_unstable();
+	patterns
+		.chunk_by(|a, b| a == b)
+		.map(|bucket| bucket.len())
+		.collect()
+}
+
+/// Expected information in bits gained by guessing `guess` when the hidden
+/// word is any of the candidates with equal probability.
+///
+/// The result is the entropy of the feedback partition, see `bucket_sizes`.
+fn expected_information(guess: &str, feedback: &Feedback) -> f32 {
+	if feedback.is_empty() {
+		return 0.0;
+	}
+	let total = feedback.len() as f32;
+	bucket_sizes(feedback.get_patterns(guess))
+		.into_iter()
+		.map(|n| {
+			let p = n as f32 / total;
+			p * (1.0 / p).log2()
+		})
+		.sum()
+}
+
+/// Number of candidates left in the worst case after guessing `guess`,
+/// i.e. size of the largest feedback bucket, see `bucket_sizes`.
+fn largest_bucket(guess: &str, feedback: &Feedback) -> usize {
+	bucket_sizes(feedback.get_patterns(guess))
+		.into_iter()
+		.max()
+		.unwrap_or(0)
 }
 
 impl Suggestion {
-	/// Constructor
-	pub fn new(word: &String) -> Self {
+	/// Constructor, frequency score is computed from given letter `frequencies`
+	pub fn new(word: &str, frequencies: &LetterFrequencyMap) -> Self {
 		Self {
 			word: String::from(word),
 			unique_chars: unique_char_count(word),
-			avg_frequency: count_avg_freq(word),
+			avg_frequency: count_avg_freq(word, word.chars().count(), frequencies),
+			positional_frequency: count_positional_freq(word, frequencies),
+			entropy: None,
+			worst_case: None,
 		}
 	}
 
 	/// Display word
 	pub fn display(&self) {
+		let entropy = match self.entropy {
+			Some(val) => format!("{:.4}", val),
+			None => "-".to_string(),
+		};
+		let worst_case = match self.worst_case {
+			Some(val) => val.to_string(),
+			None => "-".to_string(),
+		};
 		println!(
-			"{:<width$}{:<width$}{:<width$}",
+			"{:<width$}{:<width$}{:<freq_width$}{:<freq_width$.4}{:<freq_width$}{}",
 			&self.word,
 			&self.unique_chars,
 			&self.avg_frequency,
-			width = COL_WIDTH
+			&self.positional_frequency,
+			entropy,
+			worst_case,
+			width = COL_WIDTH,
+			freq_width = FREQ_COL_WIDTH
 		);
 	}
 
-	pub fn update_unique_with_info(&mut self, info: &String) {
+	pub fn update_unique_with_info(&mut self, info: &str) {
 		let s = self.word.clone() + info;
 		self.unique_chars = unique_char_count(&s);
 	}
 
-	pub fn update_freq_with_info(&mut self, info: &String) {
-		let mut positions: Vec<usize> = Vec::new();
+	/// Frequency score of the word without known letters `info`, one instance
+	/// of the letter is left out for each of its occurrences in `info`
+	pub fn update_freq_with_info(&mut self, info: &str, frequencies: &LetterFrequencyMap) {
+		let mut letters: Vec<char> = self.word.chars().collect();
+		let word_len = letters.len();
 		for c in info.chars() {
-			positions.push(self.word.chars().position(|x| x == c).unwrap());
-		}
-		positions.sort();
-		positions.reverse();
-		// dbg!(&positions);
-		let mut s = self.get_word_clone();
-		for i in positions.iter() {
-			s.remove(*i);
+			if let Some(i) = letters.iter().position(|&x| x == c) {
+				letters.remove(i);
+			}
 		}
-		self.avg_frequency = count_avg_freq(&s);
+		let s: String = letters.into_iter().collect();
+		self.avg_frequency = count_avg_freq(&s, word_len, frequencies);
+	}
+
+	/// Updates the expected information against the candidates of `feedback`
+	pub fn update_entropy(&mut self, feedback: &Feedback) {
+		self.entropy = Some(expected_information(&self.word, feedback));
+	}
+
+	/// Updates the worst case bucket size against the candidates of `feedback`
+	pub fn update_worst_case(&mut self, feedback: &Feedback) {
+		self.worst_case = Some(largest_bucket(&self.word, feedback));
 	}
 
 	pub fn get_unique_chars(&self) -> i8 {
 		self.unique_chars
 	}
 
-	pub fn get_word_clone(&self) -> String {
-		self.word.clone()
+	pub fn get_avg_frequency(&self) -> f32 {
+		self.avg_frequency
 	}
-}
 
-impl Default for Suggestion {
-	fn default() -> Self {
-		Self {
-			word: String::new(),
-			avg_frequency: 0.0,
-			unique_chars: 0,
-		}
+	pub fn get_positional_frequency(&self) -> f32 {
+		self.positional_frequency
+	}
+
+	pub fn get_entropy(&self) -> Option<f32> {
+		self.entropy
+	}
+
+	pub fn get_worst_case(&self) -> Option<usize> {
+		self.worst_case
+	}
+
+	pub fn get_word(&self) -> &str {
+		&self.word
+	}
+
+	pub fn get_word_clone(&self) -> String {
+		self.word.clone()
 	}
 }
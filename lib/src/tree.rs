@@ -0,0 +1,388 @@
+//! Precomputed decision tree module
+//!
+//! A decision tree holds the guess to play for every sequence of feedback
+//! the game can respond with, so a whole game is played by lookups only.
+
+use crate::assistant::{Assistant, UpdateError};
+use crate::database::{normalize_word, Database};
+use crate::game::{WordStatus, Wordle};
+use crate::suggestion::SortMode;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Deeper branches are not built, words left there count as unsolved
+const MAX_DEPTH: usize = 20;
+/// Best ranked guesses tried at every branch to fit the tree into the allowed guesses
+const BRANCH_WIDTH: usize = 8;
+
+/// Errors that can occur while loading the tree
+///
+/// Line numbers are 1-based, as shown by text editors.
+#[derive(Debug)]
+pub enum TreeError {
+	/// File could not be opened or read
+	Io(io::Error),
+	/// File contains no guesses
+	Empty,
+	/// Line is not a feedback path followed by a tab and a guess
+	Malformed { line: usize },
+	/// Feedback path at `line` continues a branch that is not defined above it
+	Orphan { line: usize },
+}
+
+impl fmt::Display for TreeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Io(err) => write!(f, "{}", err),
+			Self::Empty => write!(f, "tree contains no guesses"),
+			Self::Malformed { line } => {
+				write!(f, "line {}: expected feedback path, tab and guess", line)
+			}
+			Self::Orphan { line } => write!(f, "line {}: parent branch is not defined", line),
+		}
+	}
+}
+
+impl Error for TreeError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::Io(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for TreeError {
+	fn from(err: io::Error) -> Self {
+		Self::Io(err)
+	}
+}
+
+/// Guess to play and subtrees keyed by the feedback to it, e.g. `XXYXG`
+///
+/// Solving feedback has no subtree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TreeNode {
+	pub guess: String,
+	pub children: BTreeMap<String, TreeNode>,
+}
+
+impl TreeNode {
+	fn new(guess: String) -> Self {
+		Self {
+			guess,
+			children: BTreeMap::new(),
+		}
+	}
+}
+
+/// Statistics of solving every word of a solution list by the tree
+#[derive(Debug, Default, Clone)]
+pub struct TreeStats {
+	/// `histogram[n]` is number of words solved with `n` guesses
+	pub histogram: Vec<usize>,
+	/// Words the tree has no branch for
+	pub unsolved: Vec<String>,
+	/// Words solved only with more guesses than the game allows
+	pub failed: Vec<String>,
+}
+
+impl TreeStats {
+	/// Number of solved words
+	pub fn get_solved(&self) -> usize {
+		self.histogram.iter().sum()
+	}
+
+	/// Number of words solved within the allowed guesses
+	pub fn get_won(&self) -> usize {
+		self.get_solved() - self.failed.len()
+	}
+
+	/// Most guesses needed to solve any of the solved words
+	pub fn get_max_depth(&self) -> usize {
+		self.histogram.len().saturating_sub(1)
+	}
+
+	/// Average number of guesses over the solved words
+	pub fn get_mean_depth(&self) -> f32 {
+		let total: usize = self
+			.histogram
+			.iter()
+			.enumerate()
+			.map(|(depth, count)| depth * count)
+			.sum();
+		total as f32 / self.get_solved().max(1) as f32
+	}
+}
+
+/// Solving tree
+///
+/// Built by playing the `Assistant` strategy against every feedback possible
+/// for the remaining candidates. Every branch takes the best ranked guess
+/// whose subtree solves all its candidates within the allowed guesses,
+/// trying up to `BRANCH_WIDTH` guesses before backtracking. The worst case is
+/// thus bounded, but the tree is not guaranteed to be the shallowest one.
+/// Saved as plain text, one guess per line
+/// preceded by the feedback path leading to it and a tab, e.g.
+/// `XXYXG GXXXX<TAB>could`. The first guess has an empty path.
+///
+/// # Example
+/// ```
+/// use lib::database::Database;
+/// use lib::suggestion::SortMode;
+/// use lib::tree::DecisionTree;
+///
+/// let solutions = Database::load("../data/test_dict.txt");
+/// let tree = DecisionTree::build(&solutions, None, SortMode::Entropy, false, 6).unwrap();
+/// let stats = tree.stats(&solutions, 6);
+/// assert_eq!(stats.get_solved(), solutions.get_len());
+/// assert!(stats.unsolved.is_empty());
+/// assert!(stats.failed.is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DecisionTree {
+	root: TreeNode,
+}
+
+impl DecisionTree {
+	/// Builds the tree for `solutions`, optionally using probe words from `guesses`
+	///
+	/// Without `guesses` the solutions are used as probe words as well.
+	/// Guesses are ranked by `Assistant::get_ranked` in given `mode`, in hard mode
+	/// only guesses respecting revealed hints are used. Feedback used for scoring
+	/// is precomputed, see `Assistant::precompute_patterns`.
+	///
+	/// Every word is solved within `max_attempts` guesses if the search finds
+	/// such tree, otherwise the best ranked guesses are played without the limit
+	/// and words over it are reported by `TreeStats::failed`.
+	///
+	/// Panics if the solution list is empty or pools have different word length
+	pub fn build(
+		solutions: &Database,
+		guesses: Option<&Database>,
+		mode: SortMode,
+		hard: bool,
+		max_attempts: usize,
+	) -> Result<Self, UpdateError> {
+		// Any solution can be played to split the candidates if no guess pool is given
+		let guesses = guesses.unwrap_or(solutions).clone();
+		let mut assistant = Assistant::from_databases(solutions.clone(), Some(guesses));
+		assistant.set_sort_mode(mode);
+		assistant.set_hard_mode(hard);
+		assistant.precompute_patterns();
+		let root = match search_node(&mut assistant, 1, max_attempts)? {
+			Some(root) => root,
+			None => build_node(&mut assistant, 1)?.expect("Empty solution list"),
+		};
+		Ok(Self { root })
+	}
+
+	/// First guess
+	pub fn get_root(&self) -> &TreeNode {
+		&self.root
+	}
+
+	/// Guess to play after `history` of guesses and their feedback
+	///
+	/// `None` if the history leaves the tree, i.e. other than tree's guesses were played
+	/// or the feedback is not possible for the tree's solution list.
+	pub fn get_guess(&self, history: &[(String, WordStatus)]) -> Option<&str> {
+		let mut node = &self.root;
+		for (word, status) in history.iter() {
+			if *word != node.guess {
+				return None;
+			}
+			node = node.children.get(&status.as_string())?;
+		}
+		Some(&node.guess)
+	}
+
+	/// Number of guesses needed to solve `word` or `None` if the tree has no branch for it
+	pub fn solve(&self, word: &str) -> Option<usize> {
+		let mut node = &self.root;
+		for depth in 1..=MAX_DEPTH {
+			let status = feedback(word, &node.guess);
+			if status.is_correct() {
+				return Some(depth);
+			}
+			node = node.children.get(&status.as_string())?;
+		}
+		None
+	}
+
+	/// Plays every word of `solutions` by the tree, words needing more
+	/// than `max_attempts` guesses are counted as failed
+	pub fn stats(&self, solutions: &Database, max_attempts: usize) -> TreeStats {
+		let mut stats = TreeStats::default();
+		for word in solutions.get_available().iter() {
+			match self.solve(word) {
+				Some(depth) => {
+					if stats.histogram.len() <= depth {
+						stats.histogram.resize(depth + 1, 0);
+					}
+					stats.histogram[depth] += 1;
+					if depth > max_attempts {
+						stats.failed.push(word.clone());
+					}
+				}
+				None => stats.unsolved.push(word.clone()),
+			}
+		}
+		stats
+	}
+
+	/// Writes the tree to a file, see `DecisionTree` for the format
+	pub fn save<P>(&self, filename: P) -> io::Result<()>
+	where
+		P: AsRef<Path> + Debug,
+	{
+		let mut writer = BufWriter::new(File::create(filename)?);
+		write_node(&mut writer, &mut Vec::new(), &self.root)?;
+		writer.flush()
+	}
+
+	/// Reads a tree written by `DecisionTree::save`
+	///
+	/// Every line has to come after the line of its parent branch.
+	pub fn load<P>(filename: P) -> Result<Self, TreeError>
+	where
+		P: AsRef<Path> + Debug,
+	{
+		let reader = BufReader::new(File::open(filename)?);
+		let mut root: Option<TreeNode> = None;
+		for (i, line) in reader.lines().enumerate() {
+			let line = line?;
+			let line_number = i + 1;
+			if line.trim().is_empty() {
+				continue;
+			}
+			let (path, guess) = line
+				.split_once('\t')
+				.ok_or(TreeError::Malformed { line: line_number })?;
+			let guess = normalize_word(guess);
+			if guess.is_empty() {
+				return Err(TreeError::Malformed { line: line_number });
+			}
+			let path: Vec<String> = path.split_whitespace().map(str::to_uppercase).collect();
+			let (last, parents) = match path.split_last() {
+				Some(split) => split,
+				None => {
+					root = Some(TreeNode::new(guess));
+					continue;
+				}
+			};
+			let mut node = root
+				.as_mut()
+				.ok_or(TreeError::Orphan { line: line_number })?;
+			for feedback in parents.iter() {
+				node = node
+					.children
+					.get_mut(feedback)
+					.ok_or(TreeError::Orphan { line: line_number })?;
+			}
+			node.children.insert(last.clone(), TreeNode::new(guess));
+		}
+		root.map(|root| Self { root }).ok_or(TreeError::Empty)
+	}
+}
+
+/// Feedback the game responds with to `guess` when `word` is the hidden one
+fn feedback(word: &str, guess: &str) -> WordStatus {
+	Wordle::new(word.to_string())
+		.guess_word(guess)
+		.expect("Guess of different length than the hidden word")
+}
+
+/// Feedback `guess` can get from the current candidates, except the solving one
+fn branch_patterns(assistant: &Assistant, guess: &str) -> Vec<String> {
+	let mut patterns: Vec<String> = assistant
+		.get_candidates()
+		.iter()
+		.map(|candidate| feedback(candidate, guess))
+		.filter(|status| !status.is_correct())
+		.map(|status| status.as_string())
+		.collect();
+	patterns.sort();
+	patterns.dedup();
+	patterns
+}
+
+/// Builds subtree for the current candidates of the `assistant` solving all of them
+/// by guess number `max_depth`, `None` if none of the tried guesses manages that
+///
+/// `depth` is the number of the guess played at this node. The assistant is
+/// updated by every possible feedback in turn and then rolled back to
+/// a checkpoint, so it ends in the same state.
+fn search_node(
+	assistant: &mut Assistant,
+	depth: usize,
+	max_depth: usize,
+) -> Result<Option<TreeNode>, UpdateError> {
+	let guesses = if depth < max_depth {
+		assistant.get_ranked(BRANCH_WIDTH)
+	} else if assistant.get_candidates().len() == 1 {
+		// Last guess has to be the answer
+		assistant.get_candidates().iter().cloned().collect()
+	} else {
+		Vec::new()
+	};
+	let checkpoint = assistant.checkpoint();
+	'guesses: for guess in guesses {
+		let mut node = TreeNode::new(guess);
+		for pattern in branch_patterns(assistant, &node.guess) {
+			assistant.try_update(&node.guess, &pattern)?;
+			let child = search_node(assistant, depth + 1, max_depth);
+			assistant.rollback(&checkpoint);
+			match child? {
+				Some(child) => node.children.insert(pattern, child),
+				None => continue 'guesses,
+			};
+		}
+		return Ok(Some(node));
+	}
+	Ok(None)
+}
+
+/// Builds subtree for the current candidates of the `assistant` by the best
+/// guesses only, branches deeper than `MAX_DEPTH` are left out
+///
+/// The assistant ends in the same state, see `search_node`.
+fn build_node(assistant: &mut Assistant, depth: usize) -> Result<Option<TreeNode>, UpdateError> {
+	let mut node = match assistant.get_best() {
+		Some(guess) => TreeNode::new(guess),
+		None => return Ok(None),
+	};
+	if depth >= MAX_DEPTH {
+		return Ok(Some(node));
+	}
+	let checkpoint = assistant.checkpoint();
+	for pattern in branch_patterns(assistant, &node.guess) {
+		assistant.try_update(&node.guess, &pattern)?;
+		let child = build_node(assistant, depth + 1);
+		assistant.rollback(&checkpoint);
+		if let Some(child) = child? {
+			node.children.insert(pattern, child);
+		}
+	}
+	Ok(Some(node))
+}
+
+fn write_node<W: Write>(writer: &mut W, path: &mut Vec<String>, node: &TreeNode) -> io::Result<()> {
+	writeln!(writer, "{}\t{}", path.join(" "), node.guess)?;
+	for (pattern, child) in node.children.iter() {
+		path.push(pattern.clone());
+		write_node(writer, path, child)?;
+		path.pop();
+	}
+	Ok(())
+}
@@ -0,0 +1,113 @@
+//! Feedback of `Wordle::guess_word` checked against a reference implementation
+//! of the official two-pass algorithm: greens first, then yellows left to right
+//! while unmatched letters of the hidden word remain.
+
+use lib::database::Database;
+use lib::game::Wordle;
+use lib::pattern::Pattern;
+use proptest::prelude::*;
+use std::collections::HashMap;
+
+/// Status string of `guess` against hidden `word`, e.g. `GYXXY`
+fn reference(word: &str, guess: &str) -> String {
+	let word: Vec<char> = word.chars().collect();
+	let guess: Vec<char> = guess.chars().collect();
+	let mut result = vec!['X'; guess.len()];
+	let mut remaining: HashMap<char, usize> = HashMap::new();
+	for i in 0..word.len() {
+		if word[i] == guess[i] {
+			result[i] = 'G';
+		} else {
+			*remaining.entry(word[i]).or_insert(0) += 1;
+		}
+	}
+	for i in 0..guess.len() {
+		if result[i] == 'G' {
+			continue;
+		}
+		if let Some(count) = remaining.get_mut(&guess[i]).filter(|count| **count > 0) {
+			*count -= 1;
+			result[i] = 'Y';
+		}
+	}
+	result.into_iter().collect()
+}
+
+fn guess_word(word: &str, guess: &str) -> String {
+	Wordle::new(word.to_string())
+		.guess_word(guess)
+		.expect("Guess rejected")
+		.as_string()
+}
+
+#[test]
+fn known_duplicate_letter_cases() {
+	for (word, guess, expected) in [
+		("abide", "speed", "XXYXY"),
+		("abide", "eerie", "XXXYG"),
+		("crane", "cover", "GXXYY"),
+		("speed", "abide", "XXXYY"),
+		("mamma", "maxim", "GGXXY"),
+		("belle", "level", "YGXYY"),
+	] {
+		assert_eq!(reference(word, guess), expected, "{} vs {}", guess, word);
+		assert_eq!(guess_word(word, guess), expected, "{} vs {}", guess, word);
+	}
+}
+
+fn check_all_pairs(path: &str) {
+	let database = Database::load(path);
+	let words = database.get_available();
+	for word in words.iter() {
+		for guess in words.iter() {
+			let expected = reference(word, guess);
+			assert_eq!(guess_word(word, guess), expected, "{} vs {}", guess, word);
+			assert_eq!(
+				Pattern::new(word, guess).to_status(guess).as_string(),
+				expected,
+				"{} vs {}",
+				guess,
+				word
+			);
+		}
+	}
+}
+
+#[test]
+fn all_pairs_of_test_dict() {
+	check_all_pairs("../data/test_dict.txt");
+}
+
+#[test]
+fn all_pairs_of_11_letter_dict() {
+	check_all_pairs("../data/test_dict_11.txt");
+}
+
+/// Word pairs of equal length over a small alphabet, so letters repeat often
+fn word_pair() -> impl Strategy<Value = (String, String)> {
+	(1..=Pattern::MAX_LEN).prop_flat_map(|len| {
+		let letters = prop::sample::select(vec!['a', 'b', 'c', 'č', 'e']);
+		let word = prop::collection::vec(letters, len).prop_map(String::from_iter);
+		(word.clone(), word)
+	})
+}
+
+proptest! {
+	#[test]
+	fn guess_word_matches_reference((word, guess) in word_pair()) {
+		let expected = reference(&word, &guess);
+		prop_assert_eq!(guess_word(&word, &guess), expected.clone());
+		prop_assert_eq!(Pattern::new(&word, &guess).to_status(&guess).as_string(), expected);
+	}
+
+	#[test]
+	fn marks_no_more_letters_than_the_word_has((word, guess) in word_pair()) {
+		let status = Wordle::new(word.clone()).guess_word(&guess).unwrap();
+		for c in guess.chars() {
+			let (green, yellow, _) = status.char_count(c);
+			let in_word = word.chars().filter(|w| *w == c).count();
+			let in_guess = guess.chars().filter(|g| *g == c).count();
+			prop_assert_eq!(green + yellow, in_word.min(in_guess));
+		}
+	}
+}
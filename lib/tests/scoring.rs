@@ -0,0 +1,106 @@
+//! Scores of the suggestions on a small dictionary with a known partition
+//!
+//! Candidates `cat car bat dog` are split by the guesses as follows:
+//! `cat`, `car` and `bar` into four singletons, `cab` into `{cat car} {bat} {dog}`
+//! and `dog` into `{cat car bat} {dog}`.
+
+use indexmap::IndexSet;
+use lib::database::Database;
+use lib::letter_frequency::LetterFrequencyMap;
+use lib::pattern::Feedback;
+use lib::suggestion::{SortMode, SuggestionCollection};
+
+const CANDIDATES: [&str; 4] = ["cat", "car", "bat", "dog"];
+const GUESSES: [&str; 5] = ["dog", "cab", "car", "cat", "bar"];
+
+fn words(words: &[&str]) -> IndexSet<String> {
+	words.iter().map(|w| w.to_string()).collect()
+}
+
+/// Guesses scored by letter frequencies of the candidates
+fn suggestions(candidates: &IndexSet<String>) -> SuggestionCollection {
+	SuggestionCollection::from_words(
+		&words(&GUESSES),
+		&LetterFrequencyMap::from_words(candidates),
+	)
+}
+
+fn order(suggestions: &SuggestionCollection) -> Vec<&str> {
+	suggestions.items.iter().map(|s| s.get_word()).collect()
+}
+
+#[test]
+fn entropy_of_known_partition() {
+	let candidates = words(&CANDIDATES);
+	let mut suggestions = suggestions(&candidates);
+	suggestions.update_entropy(&Feedback::Words(&candidates));
+	suggestions.sort_suggestions_entropy();
+	// Ties of 2 bits are ordered by frequency score
+	assert_eq!(order(&suggestions), ["cat", "car", "bar", "cab", "dog"]);
+	let entropy = |i: usize| suggestions.items[i].get_entropy().unwrap();
+	assert_eq!(entropy(0), 2.0);
+	assert_eq!(entropy(2), 2.0);
+	assert!((entropy(3) - 1.5).abs() < 1e-6);
+	let dog = 0.75 * (4.0f32 / 3.0).log2() + 0.25 * 4.0f32.log2();
+	assert!((entropy(4) - dog).abs() < 1e-6);
+}
+
+#[test]
+fn worst_case_of_known_partition() {
+	let candidates = words(&CANDIDATES);
+	let mut suggestions = suggestions(&candidates);
+	suggestions.update_worst_case(&Feedback::Words(&candidates));
+	suggestions.sort_suggestions_minimax();
+	// Ties of a single candidate left are ordered by frequency score
+	assert_eq!(order(&suggestions), ["cat", "car", "bar", "cab", "dog"]);
+	let worst: Vec<usize> = suggestions
+		.items
+		.iter()
+		.map(|s| s.get_worst_case().unwrap())
+		.collect();
+	assert_eq!(worst, [1, 1, 1, 2, 3]);
+}
+
+#[test]
+fn positional_order_of_known_frequencies() {
+	let candidates = words(&CANDIDATES);
+	let mut suggestions = suggestions(&candidates);
+	suggestions.sort_suggestions_positional();
+	// `cab` and `bar` tie at 125 / 3, `cab` has higher frequency score
+	assert_eq!(order(&suggestions), ["cat", "car", "cab", "bar", "dog"]);
+	let positional: Vec<f32> = suggestions
+		.items
+		.iter()
+		.map(|s| s.get_positional_frequency())
+		.collect();
+	let expected = [175.0 / 3.0, 150.0 / 3.0, 125.0 / 3.0, 125.0 / 3.0, 25.0];
+	for (score, expected) in positional.iter().zip(expected) {
+		assert!((score - expected).abs() < 1e-4, "{} != {}", score, expected);
+	}
+}
+
+/// Scores of `update_entropy` and `update_worst_case`, parallel with
+/// the `parallel` feature, against scoring the suggestions one by one
+#[test]
+fn collection_scoring_matches_serial_order() {
+	let database = Database::load("../data/possible_words.txt");
+	let candidates: IndexSet<String> = database.get_available().iter().take(150).cloned().collect();
+	let feedback = Feedback::Words(&candidates);
+	let mut collection = SuggestionCollection::from(database.get_available());
+	let mut serial = SuggestionCollection::from(database.get_available());
+	collection.update_entropy(&feedback);
+	collection.update_worst_case(&feedback);
+	for suggestion in serial.items.iter_mut() {
+		suggestion.update_entropy(&feedback);
+		suggestion.update_worst_case(&feedback);
+	}
+	for mode in [SortMode::Entropy, SortMode::Minimax] {
+		collection.sort_by_mode(mode);
+		serial.sort_by_mode(mode);
+		assert_eq!(order(&collection), order(&serial), "{:?}", mode);
+		for (a, b) in collection.items.iter().zip(serial.items.iter()) {
+			assert_eq!(a.get_entropy(), b.get_entropy());
+			assert_eq!(a.get_worst_case(), b.get_worst_case());
+		}
+	}
+}
@@ -1,26 +1,105 @@
 use clap::{Arg as ClapArg, Command as ClapCommand};
 use lib::assistant::Assistant;
+use lib::database::load_pools;
+use lib::suggestion::SortMode;
 use std::io::{stdin, stdout, Write};
+use std::process;
+
+/// Loads decision tree into the assistant, exits with a diagnostic on failure
+fn load_tree(assistant: &mut Assistant, path: &str) {
+	if let Err(err) = assistant.load_tree(path) {
+		eprintln!("Failed to load tree \"{}\": {}", path, err);
+		process::exit(1);
+	}
+}
 
 /// Currently used for testing
 fn main() {
 	let args = ClapCommand::new("Wordle Player")
-		.arg(ClapArg::new("dict").index(1))
+		.arg(ClapArg::new("dict").index(1).required(true))
 		.after_help("Words dictionary")
+		.arg(
+			ClapArg::new("guesses")
+				.short('g')
+				.value_name("DICT")
+				.long("guesses")
+				.takes_value(true)
+				.help("Allowed guesses dictionary, used to suggest probe words with entropy ranking"),
+		)
+		.arg(
+			ClapArg::new("hard")
+				.long("hard")
+				.help("Hard mode, revealed hints must be used in subsequent guesses"),
+		)
+		.arg(
+			ClapArg::new("sort")
+				.short('s')
+				.value_name("MODE")
+				.long("sort")
+				.takes_value(true)
+				.possible_values(SortMode::VARIANTS)
+				.help("Ranking used to pick the next guess [default: unique]"),
+		)
+		.arg(
+			ClapArg::new("tree")
+				.short('t')
+				.value_name("FILE")
+				.long("tree")
+				.takes_value(true)
+				.help("Decision tree built by the tree tool, its guesses are played while the game follows it"),
+		)
+		.arg(
+			ClapArg::new("random")
+				.long("random")
+				.conflicts_with("sort")
+				.help("Plays a random word of the ones with most unique letters instead of the most frequent one"),
+		)
+		.arg(
+			ClapArg::new("seed")
+				.value_name("NUMBER")
+				.long("seed")
+				.takes_value(true)
+				.help("Seed for reproducible random picks of the assistant"),
+		)
 		.get_matches();
 	let path = args
 		.value_of("dict")
 		.expect("dict file expected as argument");
-	let mut assistant = Assistant::new(path);
-	for _ in 0..6 {
-		let mut word = String::new();
-		let guess = assistant.get_most_freq_uniq();
-		if guess.is_some() {
-			word = guess.unwrap();
-		} else {
-			println!("Out of suggestions, did you win?");
-			break;
+	let (solutions, guesses) = load_pools(path, args.value_of("guesses")).unwrap_or_else(|err| {
+		eprintln!("Failed to load dictionary {}", err);
+		process::exit(1);
+	});
+	let mut assistant = Assistant::from_databases(solutions, guesses);
+	assistant.set_hard_mode(args.is_present("hard"));
+	// Player picks the most frequent of most unique words by default
+	let mode = args.value_of("sort").unwrap_or("unique");
+	assistant.set_sort_mode(mode.parse().unwrap());
+	if let Some(path) = args.value_of("tree") {
+		load_tree(&mut assistant, path);
+	}
+	match args.value_of("seed").map(str::parse::<u64>) {
+		Some(Ok(seed)) => assistant.set_seed(seed),
+		Some(Err(_)) => {
+			eprintln!("--seed expects a number");
+			process::exit(1);
 		}
+		None => {}
+	}
+	let random = args.is_present("random");
+	for _ in 0..6 {
+		// Decision tree still takes precedence over random picks
+		let best = match assistant.get_tree_guess() {
+			Some(guess) if random => Some(guess.to_string()),
+			_ if random => assistant.get_random(),
+			_ => assistant.get_best(),
+		};
+		let word = match best {
+			Some(guess) => guess,
+			None => {
+				println!("Out of suggestions, did you win?");
+				break;
+			}
+		};
 		println!("\nTry this next: {}", word);
 		let mut status = String::new();
 		print!("Insert status string [GYX]:");
@@ -28,6 +107,8 @@ fn main() {
 		stdin().read_line(&mut status).expect("Incorrect input");
 		status.pop();
 		status = status.to_uppercase();
-		assistant.update(&word, &status);
+		if let Err(err) = assistant.try_update(&word, &status) {
+			println!("{}", err);
+		}
 	}
 }
@@ -0,0 +1,128 @@
+use clap::{Arg as ClapArg, Command as ClapCommand};
+use lib::database::load_pools;
+use lib::suggestion::SortMode;
+use lib::tree::DecisionTree;
+use std::process;
+use std::time::Instant;
+
+const HISTOGRAM_WIDTH: usize = 50;
+/// Tree is built to solve every word within this many guesses if possible
+const WIN_ATTEMPTS: usize = 6;
+
+/// Builds decision tree for the solutions dictionary, or checks an already built one
+fn main() {
+	let args = ClapCommand::new("Wordle Decision Tree")
+		.arg(ClapArg::new("dict").index(1).required(true))
+		.after_help("Words dictionary, the tree solves every word of it")
+		.arg(
+			ClapArg::new("guesses")
+				.short('g')
+				.value_name("DICT")
+				.long("guesses")
+				.takes_value(true)
+				.help("Allowed guesses dictionary, probe words are used where those split candidates better"),
+		)
+		.arg(
+			ClapArg::new("hard")
+				.long("hard")
+				.help("Hard mode, revealed hints must be used in subsequent guesses"),
+		)
+		.arg(
+			ClapArg::new("sort")
+				.short('s')
+				.value_name("MODE")
+				.long("sort")
+				.takes_value(true)
+				.possible_values(SortMode::VARIANTS)
+				.help("Ranking used to pick the guess at every branch [default: entropy]"),
+		)
+		.arg(
+			ClapArg::new("output")
+				.short('o')
+				.value_name("FILE")
+				.long("output")
+				.takes_value(true)
+				.help("File to save the built tree to"),
+		)
+		.arg(
+			ClapArg::new("load")
+				.short('l')
+				.value_name("FILE")
+				.long("load")
+				.takes_value(true)
+				.conflicts_with_all(&["guesses", "hard", "sort", "output"])
+				.help("Checks previously saved tree instead of building one"),
+		)
+		.get_matches();
+	let path = args
+		.value_of("dict")
+		.expect("dict file expected as argument");
+	// Guesses conflict with --load, they are given only to build the tree
+	let (solutions, guesses) = load_pools(path, args.value_of("guesses")).unwrap_or_else(|err| {
+		eprintln!("Failed to load dictionary {}", err);
+		process::exit(1);
+	});
+
+	let tree = if let Some(path) = args.value_of("load") {
+		match DecisionTree::load(path) {
+			Ok(tree) => tree,
+			Err(err) => {
+				eprintln!("Failed to load tree \"{}\": {}", path, err);
+				process::exit(1);
+			}
+		}
+	} else {
+		let mode: SortMode = args.value_of("sort").unwrap_or("entropy").parse().unwrap();
+		let start = Instant::now();
+		let hard = args.is_present("hard");
+		let tree = match DecisionTree::build(&solutions, guesses.as_ref(), mode, hard, WIN_ATTEMPTS)
+		{
+			Ok(tree) => tree,
+			Err(err) => {
+				eprintln!("Failed to build tree: {}", err);
+				process::exit(1);
+			}
+		};
+		println!("Built {:?} tree in {:.2?}", mode, start.elapsed());
+		tree
+	};
+
+	let stats = tree.stats(&solutions, WIN_ATTEMPTS);
+	println!("First guess: {}", tree.get_root().guess);
+	println!(
+		"Solved: {} of {}  Won: {} ({:.2}%)  Mean guesses: {:.3}  Worst case: {}",
+		stats.get_solved(),
+		solutions.get_len(),
+		stats.get_won(),
+		100.0 * stats.get_won() as f32 / solutions.get_len().max(1) as f32,
+		stats.get_mean_depth(),
+		stats.get_max_depth()
+	);
+	let peak = stats.histogram.iter().copied().max().unwrap_or(0).max(1);
+	for (depth, count) in stats.histogram.iter().enumerate().skip(1) {
+		println!(
+			"{:>3} | {:>6} | {}",
+			depth,
+			count,
+			"#".repeat(count * HISTOGRAM_WIDTH / peak)
+		);
+	}
+	if !stats.failed.is_empty() {
+		println!(
+			"Failed (over {} guesses): {}",
+			WIN_ATTEMPTS,
+			stats.failed.join(", ")
+		);
+	}
+	if !stats.unsolved.is_empty() {
+		println!("Unsolved: {}", stats.unsolved.join(", "));
+	}
+
+	if let Some(path) = args.value_of("output") {
+		if let Err(err) = tree.save(path) {
+			eprintln!("Failed to save tree \"{}\": {}", path, err);
+			process::exit(1);
+		}
+		println!("Tree saved to {}", path);
+	}
+}